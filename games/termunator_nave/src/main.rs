@@ -1,9 +1,13 @@
-
-use termunator::utils::{clear_area, custom_print};
-use termunator::KeyCode;
+use termunator::{Color, KeyCode};
 use termunator::World;
 use termunator::Components::*;
 use termunator::Systems::*;
+use termunator::frame_buffer::FrameBuffer;
+use termunator::effects::ScreenEffect;
+use termunator::graphics::{Image, Picture};
+use termunator::particles::{Curve, ParticleEmitter};
+use termunator::sprite::Sprite;
+use std::path::{Path, PathBuf};
 
 fn main() {
     termunator::hello();
@@ -16,48 +20,231 @@ fn main() {
     let event_system = HandleEventsSystem::new();
     let draw_system = DrawSystem::new();
     let player_system = PlayerSystem::new();
+    let animation_system = AnimationSystem::new();
+    let particle_system = ParticleSystem::new();
 
     world.add_system("handle_events", event_system);
+    world.add_system("animation", animation_system);
+    world.add_system("particles", particle_system);
     world.add_system("draw", draw_system);
+    world.add_system("effects", ScreenEffectsSystem::new());
+    world.add_system("stats", StatsOverlaySystem::new());
     world.add_system("player", player_system);
+    world.add_system("screenshot", ScreenshotSystem::new());
+    world.add_system("recording", RecordingSystem::new());
 
     // Initialize player
     let player_entity = world.create_entity();
 
     // Add components to player
     let key = KeyState::new();
-    let body = Body::new(
-        vec![
-                vec![' ', '^', '^', ' '],
-                vec!['/', '0', '0', '\\'],
-                vec!['|', '=', '=', '|'],
-                vec![' ', '/', '\\', ' '],
-            ]
-        );
+    let ship = Sprite::load(assets_dir().join("ship.txt")).expect("Erro ao carregar ship.txt");
+    let body = ship.body();
+    let animation = ship.animation();
     let pos = Position::new(0.0, 5.0);
     let vel = Velocity::new(1.0, 1.0);
 
     world.add_component(player_entity, key);
     world.add_component(player_entity, body);
+    world.add_component(player_entity, animation);
     world.add_component(player_entity, pos);
     world.add_component(player_entity, vel);
 
+    // Rastro do propulsor, saindo logo abaixo da chama
+    let trail = ParticleEmitter::new(64)
+        .with_rate(30.0)
+        .with_offset(1.0, 5.0)
+        .with_area(2.0, 0.0)
+        .with_direction(90.0, 40.0)
+        .with_speed(3.0, 6.0)
+        .with_lifetime(0.2, 0.5)
+        .with_glyphs(Curve::even(&['*', '+', '.']))
+        .with_colors(Curve::even(&[Color::Yellow, Color::Red, Color::DarkGrey]));
+    world.add_component(player_entity, trail);
+
     // Initialize window
     let mut game_state = GameState::new();
     let window = termunator::World::World::init(16*2, 9).unwrap();
     game_state.window_size = window;
+    game_state.requested_size = (16*2, 9);
+    game_state.aspect_correction = true;
     world.add_component(player_entity, game_state);
+    world.add_component(player_entity, FrameBuffer::new(window.0, window.1));
 
-    'game_loop: loop {
+    // Planeta ao fundo: imagem de verdade em terminais com kitty ou sixel, meios-blocos nos outros
+    let planet_entity = world.create_entity();
+    world.add_component(planet_entity, Picture::new(planet(48), PLANET_SIZE.0, PLANET_SIZE.1));
+    world.add_component(planet_entity, planet_position(window));
+    world.add_component(planet_entity, Layer::BACKGROUND);
+
+    // Initialize starfield: duas camadas com velocidades diferentes dão a sensação de profundidade
+    let far_stars = world.create_entity();
+    world.add_component(far_stars, starfield(window, FAR_STARS));
+    world.add_component(far_stars, Position::new(0.0, 0.0));
+    world.add_component(far_stars, Layer::BACKGROUND);
+    let near_stars = world.create_entity();
+    world.add_component(near_stars, starfield(window, NEAR_STARS));
+    world.add_component(near_stars, Position::new(0.0, 0.0));
+    world.add_component(near_stars, Layer::BACKGROUND);
 
-        clear_area(window.0, window.1);
+    let explosion = Sprite::load(assets_dir().join("explosion.txt")).expect("Erro ao carregar explosion.txt");
 
+    'game_loop: loop {
+
+        // 'q' escurece a tela e sai quando o fade termina
         let keys = world.get_component::<KeyState>(player_entity).unwrap();
-        if keys.is_key_pressed(KeyCode::Char('q')) {
+        let quitting = keys.is_key_pressed(KeyCode::Char('q'));
+        let pausing = keys.is_key_pressed(KeyCode::Char('p'));
+        if quitting {
+            world.send_event(GameEvent::Effect(ScreenEffect::FadeOut { duration: 0.4 }));
+        }
+        let faded_out = world.get_resource::<Events>().is_some_and(|events| {
+            events.iter().any(|event| matches!(event, GameEvent::FadeFinished { faded_out: true }))
+        });
+        if faded_out {
             break 'game_loop;
         }
 
+        // 'p' pausa a simulação; a tela fica escurecida e com scanlines
+        if pausing {
+            let state = world.get_resource_mut::<GameState>().unwrap();
+            state.paused = !state.paused;
+            let paused = state.paused;
+            world.send_event(GameEvent::Effect(ScreenEffect::Dim(paused)));
+            world.send_event(GameEvent::Effect(ScreenEffect::Scanlines(paused)));
+        }
+
+        // Regenera o fundo quando o terminal muda de tamanho
+        let resized = world.get_resource::<Events>().and_then(|events| {
+            events.iter().find_map(|event| match event {
+                GameEvent::Resize { width, height } => Some((*width, *height)),
+                _ => None,
+            })
+        });
+        if let Some(window) = resized {
+            world.add_component(far_stars, starfield(window, FAR_STARS));
+            world.add_component(near_stars, starfield(window, NEAR_STARS));
+            world.add_component(planet_entity, planet_position(window));
+        }
+
+        // Explosões e destroços terminados saem do mundo
+        let finished: Vec<_> = world.get_resource::<Events>().map_or(Vec::new(), |events| {
+            events
+                .iter()
+                .filter_map(|event| match event {
+                    GameEvent::AnimationFinished { entity, clip } if clip == "explode" => Some(*entity),
+                    GameEvent::ParticlesFinished { entity } => Some(*entity),
+                    _ => None,
+                })
+                .collect()
+        });
+        for entity in finished {
+            world.delete_entity(entity);
+        }
+
+        // Chama o propulsor enquanto a nave sobe; pausado, a nave não acelera nem atira
+        let paused = world.get_resource::<GameState>().is_some_and(|state| state.paused);
+        let keys = world.get_component::<KeyState>(player_entity).unwrap();
+        let thrusting = !paused && keys.is_key_pressed(KeyCode::Char('w'));
+        let firing = !paused && keys.is_key_pressed(KeyCode::Char(' '));
+        if let Some(animation) = world.get_component_mut::<Animation>(player_entity) {
+            animation.play(if thrusting { "thrust" } else { "idle" });
+        }
+        if let Some(trail) = world.get_component_mut::<ParticleEmitter>(player_entity) {
+            trail.emitting = thrusting;
+        }
+
+        // Espaço dispara uma explosão logo acima da nave
+        if firing {
+            let pos = world.get_component::<Position>(player_entity).unwrap();
+            // A âncora da explosão é o seu centro
+            let blast = (pos.x + 1.5, pos.y - 2.0);
+            let explosion_entity = world.create_entity();
+            world.add_component(explosion_entity, explosion.animation());
+            world.add_component(explosion_entity, Position::new(blast.0, blast.1));
+            world.add_component(explosion_entity, Layer::EFFECTS);
+
+            // Destroços espalhados em todas as direções, puxados pela gravidade
+            let debris_entity = world.create_entity();
+            let debris = ParticleEmitter::new(16)
+                .burst(16)
+                .with_seed(debris_entity.id.wrapping_mul(2_654_435_761))
+                .with_speed(4.0, 9.0)
+                .with_lifetime(0.3, 0.7)
+                .with_gravity(6.0)
+                .with_glyphs(Curve::even(&['*', '+', '.']))
+                .with_colors(Curve::even(&[Color::White, Color::Yellow, Color::Red, Color::DarkGrey]));
+            world.add_component(debris_entity, debris);
+            world.add_component(debris_entity, Position::new(blast.0, blast.1));
+            world.add_component(debris_entity, Layer::EFFECTS);
+
+            world.send_event(GameEvent::Effect(ScreenEffect::Shake { intensity: 1.5, duration: 0.3 }));
+            world.send_event(GameEvent::Effect(ScreenEffect::Flash { color: Color::White, duration: 0.15 }));
+        }
+
        world.update(60);
     }
 
-}
\ No newline at end of file
+}
+
+// Pasta `assets` do diretório atual ou, se não houver, a do próprio jogo
+fn assets_dir() -> PathBuf {
+    let local = Path::new("assets");
+    if local.is_dir() {
+        local.to_path_buf()
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+    }
+}
+
+// Camada de estrelas: velocidade em células por segundo, células por estrela, caractere e cor
+type StarLayer = (f32, f32, char, Color);
+const FAR_STARS: StarLayer = (2.0, 16.0, '.', Color::DarkGrey);
+const NEAR_STARS: StarLayer = (5.0, 48.0, '*', Color::Grey);
+
+// Tamanho do planeta em células
+const PLANET_SIZE: (u16, u16) = (6, 3);
+
+// Canto superior direito da janela
+fn planet_position(window: (u16, u16)) -> Position {
+    Position::new(window.0.saturating_sub(PLANET_SIZE.0 + 2) as f32, 1.0)
+}
+
+// Esfera iluminada de cima à esquerda, com `size` pixels de diâmetro
+fn planet(size: u32) -> Image {
+    let mut image = Image::new(size, size);
+    let radius = size as f32 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = (x as f32 + 0.5 - radius) / radius;
+            let dy = (y as f32 + 0.5 - radius) / radius;
+            let distance = dx * dx + dy * dy;
+            if distance > 1.0 {
+                continue;
+            }
+            // Normal da superfície contra a luz vinda de cima à esquerda
+            let dz = (1.0 - distance).sqrt();
+            let light = ((-dx - dy) * 0.5 + dz * 0.7).clamp(0.0, 1.0) * 0.85 + 0.15;
+            let shade = |channel: f32| (channel * light) as u8;
+            image.set(x, y, [shade(120.0), shade(80.0), shade(220.0), 255]);
+        }
+    }
+    image
+}
+
+// Estrelas que nascem no topo da janela e descem; já começa com a tela cheia
+fn starfield(window: (u16, u16), (speed, density, glyph, color): StarLayer) -> ParticleEmitter {
+    let (width, height) = (window.0 as f32, window.1 as f32);
+    let lifetime = height / speed;
+    let mut stars = ParticleEmitter::new((width * height / density) as usize * 2 + 8)
+        .with_rate(width * speed / density)
+        .with_area(width, 0.0)
+        .with_direction(90.0, 0.0)
+        .with_speed(speed, speed)
+        .with_lifetime(lifetime, lifetime)
+        .with_glyphs(Curve::constant(glyph))
+        .with_colors(Curve::constant(color))
+        .with_seed(speed.to_bits());
+    stars.prewarm((0.0, 0.0), lifetime, 0.05);
+    stars
+}
@@ -1,4 +1,164 @@
-fn main(){
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use termunator::canvas::{Canvas, CanvasMode};
+use termunator::frame_buffer::{Cell, FrameBuffer, Rect};
+use termunator::primitives::BorderStyle;
+use termunator::Components::*;
+use termunator::Entity::Entity;
+use termunator::Systems::*;
+use termunator::World::World;
+use termunator::{Attribute, Color, KeyCode};
+
+const WIDTH: u16 = 40;
+const HEIGHT: u16 = 15;
+
+struct Ball;
+
+impl IComponent for Ball {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Rebate a bola na moldura da quadra e marca um ponto para o lado oposto à lateral atingida;
+// o canvas em Braille mostra o movimento entre as células
+struct BounceSystem {
+    score: (u32, u32),
+    scoreboard: Entity,
+}
+
+impl ISystem for BounceSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        let mut positions = components.remove(&TypeId::of::<Position>()).unwrap_or_default();
+        let mut velocities = components.remove(&TypeId::of::<Velocity>()).unwrap_or_default();
+
+        let window = components
+            .get(&TypeId::of::<GameState>())
+            .and_then(|states| states.values().next())
+            .and_then(|state| state.as_any().downcast_ref::<GameState>())
+            .map_or((WIDTH, HEIGHT), |state| state.window_size);
+        // A bola ocupa uma célula, dentro da moldura de uma célula da quadra
+        let (right, bottom) = (window.0 as f32 - 2.0, window.1 as f32 - 2.0);
+
+        if let Some(ball_map) = components.get(&TypeId::of::<Ball>()) {
+            for entity in ball_map.keys() {
+                let pos = positions
+                    .get_mut(entity)
+                    .and_then(|pos| pos.as_any_mut().downcast_mut::<Position>());
+                let vel = velocities
+                    .get_mut(entity)
+                    .and_then(|vel| vel.as_any_mut().downcast_mut::<Velocity>());
+                let (Some(pos), Some(vel)) = (pos, vel) else {
+                    continue;
+                };
+
+                if pos.x <= 1.0 || pos.x >= right {
+                    if pos.x <= 1.0 {
+                        self.score.1 += 1;
+                    } else {
+                        self.score.0 += 1;
+                    }
+                    vel.vx = -vel.vx;
+                    pos.x = pos.x.clamp(1.0, right);
+                }
+                if pos.y <= 1.0 || pos.y >= bottom {
+                    vel.vy = -vel.vy;
+                    pos.y = pos.y.clamp(1.0, bottom);
+                }
+            }
+        }
+
+        // Atualiza o placar
+        if let Some(text) = components
+            .get_mut(&TypeId::of::<Text>())
+            .and_then(|texts| texts.get_mut(&self.scoreboard))
+            .and_then(|text| text.as_any_mut().downcast_mut::<Text>())
+        {
+            text.set_content(&format!(" {}   {} ", self.score.0, self.score.1));
+        }
+
+        components.insert(TypeId::of::<Position>(), positions);
+        components.insert(TypeId::of::<Velocity>(), velocities);
+    }
+}
+
+// Desenha a quadra: moldura dupla e rede tracejada no meio
+struct CourtSystem;
+
+impl ISystem for CourtSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        let Some(frame) = components
+            .get_mut(&TypeId::of::<FrameBuffer>())
+            .and_then(|frames| frames.values_mut().next())
+            .and_then(|frame| frame.as_any_mut().downcast_mut::<FrameBuffer>())
+        else {
+            return;
+        };
+
+        let (width, height) = frame.size();
+        frame.border(Rect::new(0, 0, width, height), BorderStyle::Double, Color::DarkGreen);
+
+        let net = width as i32 / 2;
+        let dash = Cell::new('┊').with_fg(Color::DarkGrey);
+        frame.line(net, 1, net, height as i32 - 2, dash);
+    }
+}
+
+fn main() {
     termunator::hello();
-    println!("Hello Pong")
-}
\ No newline at end of file
+    println!("Hello Pong");
+
+    // Initialize World
+    let mut world = World::new();
+
+    // Scoreboard: centralizado no topo, por cima da moldura da quadra
+    let scoreboard = world.create_entity();
+    let text = Text::new(" 0   0 ")
+        .with_anchor(Anchor::Top)
+        .with_fg(Color::White)
+        .with_attr(Attribute::Bold);
+    world.add_component(scoreboard, text);
+    world.add_component(scoreboard, Layer::HUD);
+
+    world.add_system("handle_events", HandleEventsSystem::new());
+    world.add_system("bounce", BounceSystem { score: (0, 0), scoreboard });
+    world.add_system("movement", MovementSystem::new());
+    world.add_system("court", CourtSystem);
+    world.add_system("draw", DrawSystem::new());
+
+    // Initialize window
+    let window = World::init(WIDTH, HEIGHT).unwrap();
+    let mut game_state = GameState::new();
+    game_state.window_size = window;
+    game_state.requested_size = (WIDTH, HEIGHT);
+
+    let controller = world.create_entity();
+    world.add_component(controller, KeyState::new());
+    world.add_component(controller, game_state);
+    world.add_component(controller, FrameBuffer::new(window.0, window.1));
+
+    // Ball: desenhada em Braille, com resolução de 2x4 pontos por célula
+    let mut canvas = Canvas::with_cells(1, 1, CanvasMode::Braille);
+    for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        canvas.set_pixel(x, y, Color::Yellow);
+    }
+    let ball = world.create_entity();
+    world.add_component(ball, canvas);
+    world.add_component(ball, Ball);
+    world.add_component(ball, Position::new(window.0 as f32 / 2.0, window.1 as f32 / 2.0));
+    world.add_component(ball, Velocity::new(0.35, 0.15));
+
+    loop {
+        let keys = world.get_component::<KeyState>(controller).unwrap();
+        if keys.is_key_pressed(KeyCode::Char('q')) {
+            break;
+        }
+
+        world.update(60);
+    }
+}
@@ -2,6 +2,15 @@ use std::fs;
 use std::process::Command;
 use std::io::{self, Write};
 
+use termunator::ascii_art::AsciiArt;
+use termunator::capabilities::Capabilities;
+use termunator::figlet::FigletFont;
+use termunator::netpbm;
+use termunator::{Color, Stylize};
+
+// Largura das miniaturas dos jogos no menu, em colunas
+const THUMBNAIL_COLS: u16 = 16;
+
 fn main() {
     // Lê dinamicamente os jogos na pasta `games`
     let games = get_games_list();
@@ -12,16 +21,26 @@ fn main() {
         return;
     }
 
+    let thumbnails: Vec<Option<Vec<String>>> = games.iter().map(|game| game_thumbnail(game)).collect();
+
+    // Banner com o nome da engine
+    for line in FigletFont::small().render("Termunator") {
+        println!("{}", line.magenta());
+    }
+
     loop {
-        println!("\x1b[35m\nGame Launcher:\x1b[31m");
-        println!("{}. exit\x1b[33m", 0);
+        println!("{}", "\nGame Launcher:".magenta());
+        println!("{}", format!("{}. exit", 0).red());
         for (index, game) in games.iter().enumerate() {
-            println!("{}. {}", index + 1, game);
+            for line in thumbnails[index].iter().flatten() {
+                println!("   {}", line);
+            }
+            println!("{}", format!("{}. {}", index + 1, game).yellow());
         }
-        println!("\x1b[32m{}. how to make a game", games.len() + 1);
+        println!("{}", format!("{}. how to make a game", games.len() + 1).green());
 
         // Lê a escolha do usuário
-        print!("\x1b[34mEnter your choice number: \x1b[0m");
+        print!("{}", "Enter your choice number: ".blue());
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
@@ -66,16 +85,49 @@ fn get_games_list() -> Vec<String> {
     games
 }
 
+// Miniatura do jogo em ASCII art, a partir de `thumbnail.ppm` (ou .pgm, .pbm) na pasta do jogo
+fn game_thumbnail(game_name: &str) -> Option<Vec<String>> {
+    let image = ["ppm", "pgm", "pbm"]
+        .iter()
+        .find_map(|extension| netpbm::load(format!("games/{}/thumbnail.{}", game_name, extension)).ok())?;
+    let art = AsciiArt::new().with_colors(Capabilities::detect().color);
+    let body = art.convert(&image, THUMBNAIL_COLS, AsciiArt::rows_for(&image, THUMBNAIL_COLS));
+
+    let lines = body
+        .mat
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell.fg {
+                    Color::Reset => cell.symbol().to_string(),
+                    color => cell.symbol().to_string().with(color).to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    Some(lines)
+}
+
 // Executa o jogo selecionado
 fn run_game(game_name: &str) {
     let game_path = format!("games/{}", game_name); // Caminho completo para o jogo
 
     println!("Initializing game {}...", game_name);
-    let status = Command::new("cargo")
-        .arg("run")
-        .current_dir(game_path) // Define o diretório do jogo
-        .status()
-        .expect("Failed to initialize game");
+    let mut command = Command::new("cargo");
+    command.arg("run").current_dir(game_path); // Define o diretório do jogo
+
+    // TERMUNATOR_RECORD é passado ao jogo, que grava a sessão em asciicast; o caminho é resolvido
+    // aqui porque o jogo roda dentro da pasta dele
+    if let Some(record) = std::env::var_os("TERMUNATOR_RECORD").filter(|record| !record.is_empty()) {
+        match std::path::absolute(&record) {
+            Ok(record) => {
+                println!("Recording session to {}", record.display());
+                command.env("TERMUNATOR_RECORD", record);
+            }
+            Err(error) => println!("Invalid TERMUNATOR_RECORD {}: {}", record.to_string_lossy(), error),
+        }
+    }
+    let status = command.status().expect("Failed to initialize game");
 
     if !status.success() {
         println!("Error initializing game {}", game_name);
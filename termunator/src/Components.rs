@@ -8,9 +8,17 @@
 // specific characteristics and makes them flexible and easily configurable.
 // -------------------------------------------------------------------------------------------------------------------------------
 
-use std::{any::Any, collections::HashMap};
+use std::{any::Any, collections::{HashMap, VecDeque}, path::PathBuf};
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::style::{Attribute, Attributes, Color};
+
+use crate::Entity::Entity;
+use crate::frame_buffer::{Cell, Rect};
+use crate::primitives::BorderStyle;
+use crate::effects::ScreenEffect;
+use crate::screenshot::ScreenshotFormat;
+use crate::unicode;
 
 pub trait IComponent: Any {
     fn as_any(&self) -> &dyn Any;
@@ -62,16 +70,85 @@ impl IComponent for Velocity {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct Body {
-    pub mat: Vec<Vec<char>>,
+    pub mat: Vec<Vec<Cell>>,
+    // Caractere tratado como transparente: não sobrescreve o que já foi desenhado embaixo
+    pub transparent: Option<char>,
+    // Ponto do `body` (coluna, linha) que fica sobre a `Position` da entidade
+    pub anchor: (i32, i32),
 }
 
 impl Body {
+    // Caracteres largos (CJK, emoji) ganham uma célula de continuação ao lado
     pub fn new(mat: Vec<Vec<char>>) -> Self {
-        Self { mat }
+        let rows = mat
+            .into_iter()
+            .map(|row| {
+                let mut cells = Vec::with_capacity(row.len());
+                row.into_iter().for_each(|ch| unicode::push_cell(&mut cells, Cell::new(ch)));
+                cells
+            })
+            .collect();
+        Self::from_cells(pad_rows(rows))
+    }
+
+    pub fn from_cells(mat: Vec<Vec<Cell>>) -> Self {
+        Self { mat, transparent: None, anchor: (0, 0) }
+    }
+
+    // Cria um `body` a partir de linhas de texto, separadas em grafemas, completando as
+    // mais curtas com espaços
+    pub fn from_lines(lines: &[&str]) -> Self {
+        let rows = lines.iter().map(|line| unicode::cells(line)).collect();
+        Self::from_cells(pad_rows(rows))
+    }
+
+    pub fn with_anchor(mut self, x: i32, y: i32) -> Self {
+        self.anchor = (x, y);
+        self
+    }
+
+    pub fn with_transparent(mut self, ch: char) -> Self {
+        self.transparent = Some(ch);
+        self
+    }
+
+    // Uma célula é transparente se usa o caractere designado e não tem cor de fundo
+    pub fn is_transparent(&self, cell: &Cell) -> bool {
+        self.transparent == Some(cell.ch) && cell.bg == Color::Reset
+    }
+
+    // Aplica a mesma cor de frente a todas as células
+    pub fn with_fg(mut self, color: Color) -> Self {
+        self.cells_mut().for_each(|cell| cell.fg = color);
+        self
+    }
+
+    // Aplica a mesma cor de fundo a todas as células
+    pub fn with_bg(mut self, color: Color) -> Self {
+        self.cells_mut().for_each(|cell| cell.bg = color);
+        self
+    }
+
+    // Adiciona um atributo (negrito, sublinhado...) a todas as células
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.cells_mut().for_each(|cell| cell.attrs.set(attr));
+        self
+    }
+
+    fn cells_mut(&mut self) -> impl Iterator<Item = &mut Cell> {
+        self.mat.iter_mut().flatten()
     }
 }
 
+// Completa as linhas com espaços até a largura da maior
+fn pad_rows(mut rows: Vec<Vec<Cell>>) -> Vec<Vec<Cell>> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    rows.iter_mut().for_each(|row| row.resize(width, Cell::BLANK));
+    rows
+}
+
 impl IComponent for Body {
     fn as_any(&self) -> &dyn Any {
         self
@@ -91,6 +168,562 @@ impl Body {
     }
 }
 
+// Ordem de composição: camadas com `z` menor são desenhadas primeiro (ficam embaixo).
+// Entidades sem `Layer` são desenhadas em `Layer::ENTITIES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Layer {
+    pub z: i32,
+}
+
+impl Layer {
+    pub const BACKGROUND: Layer = Layer { z: -100 };
+    pub const ENTITIES: Layer = Layer { z: 0 };
+    pub const EFFECTS: Layer = Layer { z: 100 };
+    pub const HUD: Layer = Layer { z: 200 };
+
+    pub fn new(z: i32) -> Self {
+        Self { z }
+    }
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self::ENTITIES
+    }
+}
+
+impl IComponent for Layer {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Marca entidades desenhadas em coordenadas de tela, ignorando a câmera (HUD, placares...)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScreenSpace;
+
+impl IComponent for ScreenSpace {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Menor zoom aceito pela câmera: uma célula mostra 100 unidades de mundo
+pub const MIN_ZOOM: f32 = 0.01;
+
+// Câmera: define qual parte do mundo aparece na tela.
+// `x` e `y` são o canto superior esquerdo da visão em coordenadas de mundo.
+#[derive(Debug, Clone)]
+pub struct Camera {
+    pub x: f32,
+    pub y: f32,
+    pub size: (u16, u16),
+    // Células de tela por unidade de mundo; valores menores que 1 afastam a visão (minimapa).
+    // Maiores que 1 só espaçam as posições: cada glifo continua ocupando uma célula
+    zoom: f32,
+    // Entidade seguida pela câmera
+    pub target: Option<Entity>,
+    // Largura e altura da zona central onde o alvo se move sem arrastar a câmera
+    pub dead_zone: (f32, f32),
+    // Limites do mundo (min_x, min_y, max_x, max_y) que a câmera nunca ultrapassa
+    pub bounds: Option<(f32, f32, f32, f32)>,
+}
+
+impl Camera {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            size: (width, height),
+            zoom: 1.0,
+            target: None,
+            dead_zone: (0.0, 0.0),
+            bounds: None,
+        }
+    }
+
+    pub fn with_target(mut self, target: Entity) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn with_dead_zone(mut self, width: f32, height: f32) -> Self {
+        self.dead_zone = (width, height);
+        self
+    }
+
+    pub fn with_bounds(mut self, min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Self {
+        self.bounds = Some((min_x, min_y, max_x, max_y));
+        self
+    }
+
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.set_zoom(zoom);
+        self
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    // Zoom zero ou negativo faria a visão infinita e fica limitado a MIN_ZOOM; NaN e infinito
+    // voltam para 1
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = if zoom.is_finite() { zoom.max(MIN_ZOOM) } else { 1.0 };
+    }
+
+    // Tamanho da área visível em unidades de mundo
+    pub fn view_size(&self) -> (f32, f32) {
+        (self.size.0 as f32 / self.zoom, self.size.1 as f32 / self.zoom)
+    }
+
+    // Posição na tela sem arredondar, para quem desenha em resolução maior que a célula
+    pub fn world_to_view(&self, x: f32, y: f32) -> (f32, f32) {
+        ((x - self.x) * self.zoom, (y - self.y) * self.zoom)
+    }
+
+    pub fn world_to_screen(&self, x: f32, y: f32) -> (i32, i32) {
+        let (x, y) = self.world_to_view(x, y);
+        (x.floor() as i32, y.floor() as i32)
+    }
+
+    pub fn screen_to_world(&self, x: i32, y: i32) -> (f32, f32) {
+        (x as f32 / self.zoom + self.x, y as f32 / self.zoom + self.y)
+    }
+
+    // Move a câmera o mínimo necessário para manter o ponto dentro da zona morta
+    pub fn follow(&mut self, x: f32, y: f32) {
+        let (width, height) = self.view_size();
+        self.x = follow_axis(self.x, width, self.dead_zone.0, x);
+        self.y = follow_axis(self.y, height, self.dead_zone.1, y);
+        self.clamp();
+    }
+
+    // Mantém a visão dentro dos limites do mundo; mundos menores que a tela ficam no canto
+    pub fn clamp(&mut self) {
+        if let Some((min_x, min_y, max_x, max_y)) = self.bounds {
+            let (width, height) = self.view_size();
+            self.x = self.x.min(max_x - width).max(min_x);
+            self.y = self.y.min(max_y - height).max(min_y);
+        }
+    }
+}
+
+fn follow_axis(offset: f32, view: f32, dead_zone: f32, target: f32) -> f32 {
+    let center = offset + view / 2.0;
+    let half_zone = dead_zone.min(view) / 2.0;
+
+    if target > center + half_zone {
+        offset + (target - center - half_zone)
+    } else if target < center - half_zone {
+        offset + (target - center + half_zone)
+    } else {
+        offset
+    }
+}
+
+impl IComponent for Camera {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Área retangular da tela onde uma câmera é desenhada (tela dividida, minimapa...).
+// A câmera do viewport é o componente `Camera` da mesma entidade. Entidades `ScreenSpace`
+// (HUD) não entram nos viewports: são desenhadas uma vez sobre a tela inteira, depois deles.
+#[derive(Debug, Clone)]
+pub struct Viewport {
+    pub rect: Rect,
+    pub border: Option<BorderStyle>,
+    // Camadas desenhadas neste viewport; `None` desenha todas
+    pub layers: Option<Vec<Layer>>,
+}
+
+impl Viewport {
+    pub fn new(x: i32, y: i32, width: u16, height: u16) -> Self {
+        Self {
+            rect: Rect::new(x, y, width, height),
+            border: None,
+            layers: None,
+        }
+    }
+
+    pub fn with_border(mut self, style: BorderStyle) -> Self {
+        self.border = Some(style);
+        self
+    }
+
+    pub fn with_layers(mut self, layers: &[Layer]) -> Self {
+        self.layers = Some(layers.to_vec());
+        self
+    }
+
+    // Área disponível para o conteúdo, descontando a borda
+    pub fn inner(&self) -> Rect {
+        if self.border.is_some() {
+            self.rect.shrink(1)
+        } else {
+            self.rect
+        }
+    }
+}
+
+impl IComponent for Viewport {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+// Onde o texto é ancorado. Nas âncoras de tela, a `Position` da entidade (se houver)
+// é somada como deslocamento; em `World`, o texto fica na `Position` e segue a câmera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+    World,
+}
+
+// Texto desenhado pelo `DrawSystem` (HUD, placares, rótulos)
+#[derive(Debug, Clone)]
+pub struct Text {
+    pub content: String,
+    pub align: TextAlign,
+    pub anchor: Anchor,
+    // Largura máxima das linhas; textos maiores quebram entre palavras
+    pub wrap: Option<u16>,
+    pub fg: Color,
+    pub bg: Color,
+    pub attrs: Attributes,
+}
+
+impl Text {
+    pub fn new(content: &str) -> Self {
+        Self {
+            content: content.to_owned(),
+            align: TextAlign::Left,
+            anchor: Anchor::TopLeft,
+            wrap: None,
+            fg: Color::Reset,
+            bg: Color::Reset,
+            attrs: Attributes::none(),
+        }
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn with_wrap(mut self, width: u16) -> Self {
+        self.wrap = Some(width);
+        self
+    }
+
+    pub fn with_fg(mut self, color: Color) -> Self {
+        self.fg = color;
+        self
+    }
+
+    pub fn with_bg(mut self, color: Color) -> Self {
+        self.bg = color;
+        self
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attrs.set(attr);
+        self
+    }
+
+    pub fn set_content(&mut self, content: &str) {
+        self.content.clear();
+        self.content.push_str(content);
+    }
+
+    // Linhas do texto depois da quebra automática
+    pub fn lines(&self) -> Vec<String> {
+        self.content
+            .lines()
+            .flat_map(|line| match self.wrap {
+                Some(width) => wrap_line(line, width.max(1) as usize),
+                None => vec![line.to_owned()],
+            })
+            .collect()
+    }
+
+    // Tamanho do bloco de texto (largura da maior linha, quantidade de linhas)
+    pub fn size(&self) -> (i32, i32) {
+        let lines = self.lines();
+        let width = lines.iter().map(|line| unicode::width(line)).max().unwrap_or(0);
+        (width as i32, lines.len() as i32)
+    }
+}
+
+// Quebra uma linha entre palavras; palavras maiores que a largura são cortadas.
+// As larguras são medidas em colunas do terminal, não em `char`s.
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in line.split_whitespace() {
+        let mut parts = unicode::split_width(word, width);
+        let word = parts.pop().unwrap_or_default();
+        for part in parts {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(part);
+        }
+
+        let (current_width, word_width) = (unicode::width(&current), unicode::width(&word));
+        if current_width > 0 && current_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+impl IComponent for Text {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    // Volta ao primeiro quadro depois do último
+    Loop,
+    // Vai e volta: 0, 1, 2, 1, 0, 1...
+    PingPong,
+    // Para no último quadro e gera `GameEvent::AnimationFinished`
+    Once,
+}
+
+// Menor duração de um quadro; durações menores (ou inválidas) são arredondadas para ela
+pub const MIN_FRAME_DURATION: f32 = 0.001;
+
+// Um quadro da animação e quanto tempo ele fica na tela, em segundos
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub body: Body,
+    pub duration: f32,
+}
+
+// Sequência de quadros com nome (idle, thrust, explode...)
+#[derive(Debug, Clone)]
+pub struct Clip {
+    pub frames: Vec<Frame>,
+    pub mode: PlayMode,
+}
+
+impl Clip {
+    pub fn new(mode: PlayMode) -> Self {
+        Self { frames: Vec::new(), mode }
+    }
+
+    pub fn frame(mut self, body: Body, duration: f32) -> Self {
+        let duration = if duration.is_finite() { duration.max(MIN_FRAME_DURATION) } else { MIN_FRAME_DURATION };
+        self.frames.push(Frame { body, duration });
+        self
+    }
+}
+
+// Animação de sprite: o `AnimationSystem` avança o clipe atual e copia o quadro
+// corrente para o `Body` da entidade
+#[derive(Debug, Clone)]
+pub struct Animation {
+    pub clips: HashMap<String, Clip>,
+    // Multiplicador de velocidade da reprodução
+    pub speed: f32,
+    current: String,
+    frame: usize,
+    elapsed: f32,
+    reversing: bool,
+    finished: bool,
+    // Indica que o quadro mudou e o `Body` precisa ser atualizado
+    dirty: bool,
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Self {
+            clips: HashMap::new(),
+            speed: 1.0,
+            current: String::new(),
+            frame: 0,
+            elapsed: 0.0,
+            reversing: false,
+            finished: false,
+            dirty: true,
+        }
+    }
+
+    // Adiciona um clipe; o primeiro adicionado passa a ser o atual
+    pub fn with_clip(mut self, name: &str, clip: Clip) -> Self {
+        if self.clips.is_empty() {
+            self.current = name.to_owned();
+        }
+        self.clips.insert(name.to_owned(), clip);
+        self
+    }
+
+    // Troca para outro clipe e o reinicia; tocar o clipe atual não faz nada
+    pub fn play(&mut self, name: &str) {
+        if self.current != name && self.clips.contains_key(name) {
+            self.current = name.to_owned();
+            self.restart();
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.elapsed = 0.0;
+        self.reversing = false;
+        self.finished = false;
+        self.dirty = true;
+    }
+
+    pub fn current_clip(&self) -> &str {
+        &self.current
+    }
+
+    pub fn current_frame(&self) -> Option<&Body> {
+        self.clips
+            .get(&self.current)
+            .and_then(|clip| clip.frames.get(self.frame))
+            .map(|frame| &frame.body)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // Retorna o quadro atual se ele mudou desde a última chamada
+    pub fn take_changed_frame(&mut self) -> Option<&Body> {
+        if !std::mem::take(&mut self.dirty) {
+            return None;
+        }
+        self.current_frame()
+    }
+
+    // Avança `delta_time` segundos; retorna `true` quando um clipe `Once` acaba neste passo
+    pub fn advance(&mut self, delta_time: f32) -> bool {
+        let Some(clip) = self.clips.get(&self.current) else {
+            return false;
+        };
+        if self.finished || clip.frames.is_empty() {
+            return false;
+        }
+
+        self.elapsed += delta_time * self.speed;
+        let last = clip.frames.len() - 1;
+        // `frames` é público, então as durações são conferidas aqui também
+        let duration = |frame: usize| clip.frames[frame].duration.max(MIN_FRAME_DURATION);
+
+        // Voltas completas terminam no mesmo quadro; depois de uma pausa longa elas são descartadas
+        // em vez de percorridas uma a uma
+        let total: f32 = (0..=last).map(duration).sum();
+        let cycle = match clip.mode {
+            PlayMode::Loop => total,
+            PlayMode::PingPong if last > 0 => 2.0 * total - duration(0) - duration(last),
+            _ => f32::INFINITY,
+        };
+        if self.elapsed >= cycle {
+            self.elapsed %= cycle;
+        }
+
+        // Pode pular vários quadros se o frame demorou mais que eles
+        while self.elapsed >= duration(self.frame) {
+            self.elapsed -= duration(self.frame);
+            self.dirty = true;
+
+            match clip.mode {
+                PlayMode::Loop => self.frame = if self.frame == last { 0 } else { self.frame + 1 },
+                PlayMode::PingPong if last == 0 => {}
+                PlayMode::PingPong => {
+                    if self.frame == last {
+                        self.reversing = true;
+                    } else if self.frame == 0 {
+                        self.reversing = false;
+                    }
+                    self.frame = if self.reversing { self.frame - 1 } else { self.frame + 1 };
+                }
+                PlayMode::Once => {
+                    if self.frame == last {
+                        self.finished = true;
+                        self.elapsed = 0.0;
+                        return true;
+                    }
+                    self.frame += 1;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IComponent for Animation {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct KeyState {
     pub keys: HashMap<KeyCode, bool>, // Armazena se cada tecla está pressionada ou não
@@ -127,10 +760,19 @@ impl IComponent for KeyState {
 #[derive(Debug, Clone, Copy)]
 pub struct GameState {
     pub running: bool,
+    // Congela a simulação (movimento, jogador, animações e partículas); desenho e efeitos seguem
     pub paused: bool,
+    // Duração do último frame em segundos; zero até o primeiro `World::update`
     pub delta_time: f32,
     pub time: f32,
     pub window_size: (u16, u16),
+    // Tamanho pedido pelo jogo; a janela é recalculada a partir dele quando o terminal muda
+    pub requested_size: (u16, u16),
+    // Amplia a janela por um fator inteiro quando o terminal é bem maior que ela
+    pub upscale: bool,
+    // Cada célula lógica ocupa duas colunas, para que uma unidade tenha a mesma medida
+    // na horizontal e na vertical
+    pub aspect_correction: bool,
 }
 
 impl GameState {
@@ -138,9 +780,12 @@ impl GameState {
         Self {
             running: true,
             paused: false,
-            delta_time: 60.0,
+            delta_time: 0.0,
             time: 0.0,
             window_size: (32, 9),
+            requested_size: (32, 9),
+            upscale: false,
+            aspect_correction: false,
         }
     }
 }
@@ -153,4 +798,141 @@ impl IComponent for GameState {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
-}
\ No newline at end of file
+}
+
+// Quantos frames o `FrameStats` guarda no histórico
+pub const FRAME_HISTORY: usize = 60;
+
+// Medições dos últimos frames, atualizadas pelo `World` no fim de cada `update`
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    // Intervalo entre o início de cada frame e o do anterior, em segundos, do mais antigo ao mais novo
+    pub frame_times: VecDeque<f32>,
+    // Duração de um frame no FPS pedido ao `World::update`
+    pub target_frame_time: f32,
+    // Tempo gasto no último `update`, sem a espera do controle de FPS
+    pub work_time: f32,
+    // Tempo de cada sistema no último frame, na ordem de execução; o flush do terminal é o último
+    pub systems: Vec<(String, f32)>,
+    // Bytes enviados ao terminal no último flush
+    pub flushed_bytes: usize,
+    pub entities: usize,
+}
+
+impl FrameStats {
+    pub fn new() -> Self {
+        Self {
+            frame_times: VecDeque::with_capacity(FRAME_HISTORY),
+            target_frame_time: 0.0,
+            work_time: 0.0,
+            systems: Vec::new(),
+            flushed_bytes: 0,
+            entities: 0,
+        }
+    }
+
+    pub fn push_frame_time(&mut self, seconds: f32) {
+        if self.frame_times.len() == FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(seconds);
+    }
+
+    // Média de frames por segundo no histórico
+    pub fn fps(&self) -> f32 {
+        let total: f32 = self.frame_times.iter().sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.frame_times.len() as f32 / total
+    }
+
+    pub fn last_frame_time(&self) -> f32 {
+        self.frame_times.back().copied().unwrap_or(0.0)
+    }
+}
+
+impl IComponent for FrameStats {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Eventos gerados pela engine durante um frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    // O terminal foi redimensionado; contém o novo tamanho da janela do jogo
+    Resize { width: u16, height: u16 },
+    Key(KeyEvent),
+    // Um clipe `PlayMode::Once` chegou ao fim
+    AnimationFinished { entity: Entity, clip: String },
+    // Um emissor de partículas sem emissão contínua terminou suas rajadas
+    ParticlesFinished { entity: Entity },
+    // Dispara um efeito de tela (tremor, flash, fade...)
+    Effect(ScreenEffect),
+    // Um fade terminou: a tela está preta (`faded_out`) ou de volta ao normal
+    FadeFinished { faded_out: bool },
+    // Pede ao `ScreenshotSystem` que salve o frame atual
+    Screenshot(ScreenshotFormat),
+    // Resultado de um screenshot salvo pelo `ScreenshotSystem`
+    ScreenshotSaved { path: PathBuf },
+    ScreenshotFailed { reason: String },
+    // Gravação asciicast iniciada ou terminada, com o caminho do arquivo
+    RecordingStarted { path: PathBuf },
+    RecordingStopped { path: PathBuf },
+    RecordingFailed { reason: String },
+}
+
+// Fila de eventos do frame atual. O `World` descarta no início de cada `update` os eventos
+// do frame anterior, então os jogos devem lê-la logo depois do `update` e os sistemas durante
+// ele. Eventos enviados pelo jogo entre dois `update`s ficam para os sistemas do próximo frame.
+#[derive(Debug, Clone, Default)]
+pub struct Events {
+    pub queue: Vec<GameEvent>,
+    // Quantos eventos da fila já passaram por um frame inteiro
+    delivered: usize,
+}
+
+impl Events {
+    pub fn new() -> Self {
+        Self { queue: Vec::new(), delivered: 0 }
+    }
+
+    pub fn send(&mut self, event: GameEvent) {
+        self.queue.push(event);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GameEvent> {
+        self.queue.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.delivered = 0;
+    }
+
+    // Marca os eventos atuais como entregues; chamado pelo `World` no fim do `update`
+    pub fn mark_delivered(&mut self) {
+        self.delivered = self.queue.len();
+    }
+
+    // Descarta os eventos já entregues e mantém os enviados depois
+    pub fn retire(&mut self) {
+        self.queue.drain(..self.delivered.min(self.queue.len()));
+        self.delivered = 0;
+    }
+}
+
+impl IComponent for Events {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -8,18 +8,27 @@
 //  allowing for high flexibility and parallelization.
 // -------------------------------------------------------------------------------------------------------------------------------
 
-use crossterm::event;
 use crossterm::event::KeyCode;
-use crossterm::event::Event;
+use crossterm::style::Color;
 
 
-use crate::draw;
+use crate::{anchor_text, draw_at, draw_text, draw_with_camera};
 use crate::Entity::*;
 use crate::Components::*;
-use std::collections::HashMap;
+use crate::frame_buffer::{Cell, FrameBuffer};
+use crate::canvas::Canvas;
+use crate::graphics::Picture;
+use crate::particles::ParticleEmitter;
+use crate::tilemap::Tilemap;
+use crate::effects::ScreenEffects;
+use crate::screenshot::{self, ScreenshotFormat};
+use crate::recording;
+use crate::transform::{Tint, Transform};
+use std::collections::{HashMap, HashSet};
 use std::any::TypeId;
-
-use std::time::Duration;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub trait ISystem {
     fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>);
@@ -39,6 +48,10 @@ impl ISystem for MovementSystem {
         &mut self,
         components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>,
     ) {
+        if is_paused(components) {
+            return;
+        }
+
         // Extrai posições e velocidades em vetores temporários
         let mut positions = components
             .remove(&TypeId::of::<Position>())
@@ -81,7 +94,26 @@ impl ISystem for HandleEventsSystem {
         components: &mut HashMap<TypeId,
         HashMap<Entity, Box<dyn IComponent>>>
     )  {
-        // Remove e obtém o componente KeyState
+        // Processa os eventos de teclado coletados pelo `World` neste frame
+        let pressed: Vec<KeyCode> = components
+            .get(&TypeId::of::<Events>())
+            .and_then(|events| events.values().next())
+            .and_then(|events| events.as_any().downcast_ref::<Events>())
+            .map(|events| {
+                events
+                    .iter()
+                    .filter_map(|event| match event {
+                        GameEvent::Key(key_event) => match key_event.code {
+                            KeyCode::Char(letra) => Some(KeyCode::Char(letra)),
+                            _ => None, // Ignore outras teclas
+                        },
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Obtém o componente KeyState
         let keys_component_map = components
             .get_mut(&TypeId::of::<KeyState>())
             .expect("KeyState component not found");
@@ -99,27 +131,8 @@ impl ISystem for HandleEventsSystem {
             key_state_component.update_key_state(key, false);
         }
 
-        // Libera teclas que não estão mais pressionadas
-        let pressed_keys: Vec<KeyCode> = key_state_component.keys.keys().cloned().collect();
-        for key in pressed_keys {
-            key_state_component.update_key_state(key, false);
-        }
-
-        // Processa eventos e atualiza estados
-        if event::poll(Duration::from_millis(0)).expect("Erro ao verificar input") {
-            if let Event::Key(key_event) = event::read().expect("Erro ao ler evento") {
-                match key_event.code {
-                    KeyCode::Char(letra) => {
-                        key_state_component.update_key_state(KeyCode::Char(letra), true);
-                    },
-                    _ => {} // Ignore outras teclas
-                }
-            }
-        }
-
-        // Limpa eventos pendentes para evitar acúmulo de inputs
-        while event::poll(Duration::from_millis(0)).unwrap() {
-            event::read().unwrap(); // Lê e descarta o evento
+        for key in pressed {
+            key_state_component.update_key_state(key, true);
         }
     }
 }
@@ -134,42 +147,215 @@ impl DrawSystem {
 
 impl ISystem for DrawSystem {
     fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
-        // Extrai bodies e posições em vetores temporários
-        let mut bodies = components
-            .remove(&TypeId::of::<Body>())
-            .unwrap_or_default();
+        // Extrai o frame buffer; sem ele não há onde desenhar
+        let Some(mut frames) = components.remove(&TypeId::of::<FrameBuffer>()) else {
+            return;
+        };
 
-        let mut positions = components
-            .remove(&TypeId::of::<Position>())
-            .unwrap_or_default();
+        if let Some(frame) = frames
+            .values_mut()
+            .next()
+            .and_then(|component| component.as_any_mut().downcast_mut::<FrameBuffer>())
+        {
+            let cameras = components.get(&TypeId::of::<Camera>());
+            let camera_of = |entity: &Entity| {
+                cameras
+                    .and_then(|cameras| cameras.get(entity))
+                    .and_then(|camera| camera.as_any().downcast_ref::<Camera>())
+            };
+
+            // Viewports ordenados pelo id da entidade para uma composição estável
+            let mut viewports: Vec<(Entity, &Viewport)> = components
+                .get(&TypeId::of::<Viewport>())
+                .map(|viewports| {
+                    viewports
+                        .iter()
+                        .filter_map(|(entity, viewport)| {
+                            Some((*entity, viewport.as_any().downcast_ref::<Viewport>()?))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            viewports.sort_by_key(|(entity, _)| entity.id);
+
+            if viewports.is_empty() {
+                // Sem viewports, usa a primeira câmera encontrada na tela inteira;
+                // sem câmera, o mundo coincide com a tela
+                let camera = cameras
+                    .and_then(|cameras| cameras.values().next())
+                    .and_then(|camera| camera.as_any().downcast_ref::<Camera>());
+                render_view(frame, components, camera, None, Pass::All);
+            } else {
+                for (entity, viewport) in viewports {
+                    frame.set_view(Some(viewport.inner()));
+                    render_view(frame, components, camera_of(&entity), viewport.layers.as_deref(), Pass::World);
+                    frame.set_view(None);
+
+                    if let Some(style) = viewport.border {
+                        frame.border(viewport.rect, style, Color::Reset);
+                    }
+                }
 
-        let mut game_states = components
-            .remove(&TypeId::of::<GameState>())
-            .unwrap_or_default();
+                // O HUD é desenhado uma única vez sobre a tela inteira, por cima dos viewports
+                render_view(frame, components, None, None, Pass::Screen);
+            }
+        }
 
-        // Itera sobre todas as entidades com bodies, posições e estados de jogo
-        for (entity, pos) in positions.iter_mut() {
-            if let Some(body) = bodies.get_mut(entity) {
-                // Usa downcast para converter o `Box<dyn IComponent>` de volta para `Body`
-                if let Some(body) = body.as_any_mut().downcast_mut::<Body>() {
-                    // Obtém o estado de jogo correspondente
-                    if let Some(state) = game_states.get_mut(entity) {
-                        if let Some(state) = state.as_any_mut().downcast_mut::<GameState>() {
+        // Reinsere o frame buffer no HashMap
+        components.insert(TypeId::of::<FrameBuffer>(), frames);
+    }
+}
 
-                            // Desenha
-                            draw(body, pos.as_any().downcast_ref::<Position>().unwrap(), &state.window_size);
+// Busca o componente `T` de uma entidade diretamente no HashMap de componentes
+fn component<'a, T: IComponent>(
+    components: &'a HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>,
+    entity: &Entity,
+) -> Option<&'a T> {
+    components
+        .get(&TypeId::of::<T>())?
+        .get(entity)?
+        .as_any()
+        .downcast_ref::<T>()
+}
 
-                        }
-                    }
+// Com o jogo pausado os sistemas de simulação (movimento, jogador, animações e partículas) não
+// avançam; o desenho, os efeitos e as capturas continuam
+fn is_paused(components: &HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) -> bool {
+    components
+        .get(&TypeId::of::<GameState>())
+        .and_then(|states| states.values().next())
+        .and_then(|state| state.as_any().downcast_ref::<GameState>())
+        .is_some_and(|state| state.paused)
+}
+
+// Entidades desenhadas em cada passada do `render_view`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pass {
+    All,
+    // Só o mundo, sem as entidades `ScreenSpace` (dentro de um viewport)
+    World,
+    // Só as entidades `ScreenSpace`, sobre a tela inteira
+    Screen,
+}
+
+// Desenha as entidades visíveis pela câmera, na ordem das camadas, na visão atual do frame buffer
+fn render_view(
+    frame: &mut FrameBuffer,
+    components: &HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>,
+    camera: Option<&Camera>,
+    mask: Option<&[Layer]>,
+    pass: Pass,
+) {
+    // Entidades com algo para desenhar: bodies, canvas, tilemaps e imagens precisam de posição, textos não
+    let mut entities: HashSet<Entity> = HashSet::new();
+    for type_id in [TypeId::of::<Body>(), TypeId::of::<Canvas>(), TypeId::of::<Tilemap>(), TypeId::of::<Picture>()] {
+        if let Some(map) = components.get(&type_id) {
+            entities.extend(
+                map.keys()
+                    .filter(|entity| component::<Position>(components, entity).is_some()),
+            );
+        }
+    }
+    if let Some(texts) = components.get(&TypeId::of::<Text>()) {
+        entities.extend(texts.keys());
+    }
+    // Partículas já estão em coordenadas do mundo
+    if let Some(emitters) = components.get(&TypeId::of::<ParticleEmitter>()) {
+        entities.extend(emitters.keys());
+    }
+
+    // Ordena por camada e depois pelo id da entidade, para uma composição estável
+    let mut drawables: Vec<(Layer, Entity)> = entities
+        .into_iter()
+        .map(|entity| {
+            let layer = component::<Layer>(components, &entity).copied().unwrap_or_default();
+            (layer, entity)
+        })
+        .filter(|(layer, _)| mask.is_none_or(|mask| mask.contains(layer)))
+        .filter(|(_, entity)| match pass {
+            Pass::All => true,
+            Pass::World => component::<ScreenSpace>(components, entity).is_none(),
+            Pass::Screen => component::<ScreenSpace>(components, entity).is_some(),
+        })
+        .collect();
+    drawables.sort_by_key(|(layer, entity)| (*layer, entity.id));
+
+    for (_, entity) in drawables {
+        let pos = component::<Position>(components, &entity);
+
+        // Elementos fixos na tela ignoram a câmera
+        let fixed = component::<ScreenSpace>(components, &entity).is_some();
+        let camera = camera.filter(|_| !fixed);
+
+        if let Some(text) = component::<Text>(components, &entity) {
+            let (x, y) = match (text.anchor, pos) {
+                (Anchor::World, Some(pos)) => match camera {
+                    Some(camera) => camera.world_to_screen(pos.x, pos.y),
+                    None => (pos.x.floor() as i32, pos.y.floor() as i32),
+                },
+                (_, pos) => {
+                    let offset = pos.map_or((0, 0), |pos| (pos.x.floor() as i32, pos.y.floor() as i32));
+                    anchor_text(text, offset, frame)
                 }
+            };
+            draw_text(text, x, y, frame);
+        }
+
+        if let Some(emitter) = component::<ParticleEmitter>(components, &entity) {
+            for particle in emitter.particles() {
+                let (x, y) = match camera {
+                    Some(camera) => camera.world_to_screen(particle.x, particle.y),
+                    None => (particle.x.floor() as i32, particle.y.floor() as i32),
+                };
+                // Mantém o fundo já desenhado embaixo da partícula
+                let Some(below) = frame.get(x, y).copied() else {
+                    continue;
+                };
+                let (glyph, color) = emitter.appearance(particle);
+                frame.set(x, y, Cell::new(glyph).with_fg(color).with_bg(below.bg));
             }
         }
 
-        // Reinsere os componentes atualizados no HashMap
-        components.insert(TypeId::of::<Body>(), bodies);
-        components.insert(TypeId::of::<Position>(), positions);
-        components.insert(TypeId::of::<GameState>(), game_states);
+        let Some(pos) = pos else {
+            continue;
+        };
+
+        if let Some(tilemap) = component::<Tilemap>(components, &entity) {
+            let (left, top, zoom) = match camera {
+                Some(camera) => (camera.x - pos.x, camera.y - pos.y, camera.zoom()),
+                None => (-pos.x, -pos.y, 1.0),
+            };
+            tilemap.render(frame, left, top, zoom);
+        }
 
+        if let Some(picture) = component::<Picture>(components, &entity) {
+            let (x, y) = match camera {
+                Some(camera) => camera.world_to_screen(pos.x, pos.y),
+                None => (pos.x.floor() as i32, pos.y.floor() as i32),
+            };
+            frame.draw_picture(x, y, picture);
+        }
+
+        if let Some(body) = component::<Body>(components, &entity) {
+            // Transformações e tinta são aplicadas numa cópia; o `body` original não muda
+            let mut body = Cow::Borrowed(body);
+            if let Some(transform) = component::<Transform>(components, &entity).filter(|t| !t.is_identity()) {
+                body = Cow::Owned(body.transformed(transform));
+            }
+            if let Some(tint) = component::<Tint>(components, &entity) {
+                body = Cow::Owned(body.tinted(tint));
+            }
+
+            match camera {
+                Some(camera) => draw_with_camera(&body, pos, camera, frame),
+                None => draw_at(&body, pos.x.floor() as i32, pos.y.floor() as i32, frame),
+            }
+        }
+
+        if let Some(canvas) = component::<Canvas>(components, &entity) {
+            let (x, y) = camera.map_or((pos.x, pos.y), |camera| camera.world_to_view(pos.x, pos.y));
+            canvas.render(frame, x, y);
+        }
     }
 }
 
@@ -184,6 +370,10 @@ impl PlayerSystem {
 // Definição do sistema de jogador
 impl ISystem for PlayerSystem {
     fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        if is_paused(components) {
+            return;
+        }
+
         // Remove `bodies`, `positions` e `velocities` do HashMap temporariamente
         let mut positions = components.remove(&TypeId::of::<Position>()).unwrap_or_default();
         let velocities = components.remove(&TypeId::of::<Velocity>()).unwrap_or_default();
@@ -230,4 +420,559 @@ impl ISystem for PlayerSystem {
         components.insert(TypeId::of::<Position>(), positions);
         components.insert(TypeId::of::<Velocity>(), velocities);
     }
-}
\ No newline at end of file
+}
+
+pub struct CameraSystem;
+
+impl CameraSystem {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CameraSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Move as câmeras para acompanhar seus alvos; deve rodar antes do `DrawSystem`
+impl ISystem for CameraSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        let Some(mut cameras) = components.remove(&TypeId::of::<Camera>()) else {
+            return;
+        };
+
+        let positions = components.get(&TypeId::of::<Position>());
+        let bodies = components.get(&TypeId::of::<Body>());
+        let viewports = components.get(&TypeId::of::<Viewport>());
+
+        for (entity, camera) in cameras.iter_mut() {
+            let Some(camera) = camera.as_any_mut().downcast_mut::<Camera>() else {
+                continue;
+            };
+
+            // A câmera de um viewport enxerga exatamente a área interna dele
+            if let Some(viewport) = viewports
+                .and_then(|viewports| viewports.get(entity))
+                .and_then(|viewport| viewport.as_any().downcast_ref::<Viewport>())
+            {
+                let inner = viewport.inner();
+                camera.size = (inner.width, inner.height);
+            }
+
+            let target = camera.target.and_then(|target| {
+                let pos = positions?.get(&target)?.as_any().downcast_ref::<Position>()?;
+                // Segue o centro do `body` do alvo, se houver
+                let (center_x, center_y) = bodies
+                    .and_then(|bodies| bodies.get(&target))
+                    .and_then(|body| body.as_any().downcast_ref::<Body>())
+                    .map_or((0.0, 0.0), |body| {
+                        let (width, height) = body.size();
+                        (
+                            width as f32 / 2.0 - body.anchor.0 as f32,
+                            height as f32 / 2.0 - body.anchor.1 as f32,
+                        )
+                    });
+                Some((pos.x + center_x, pos.y + center_y))
+            });
+
+            match target {
+                Some((x, y)) => camera.follow(x, y),
+                None => camera.clamp(),
+            }
+        }
+
+        components.insert(TypeId::of::<Camera>(), cameras);
+    }
+}
+
+pub struct AnimationSystem;
+
+impl AnimationSystem {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AnimationSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Avança as animações e copia o quadro atual para o `Body` de cada entidade
+impl ISystem for AnimationSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        if is_paused(components) {
+            return;
+        }
+        let Some(mut animations) = components.remove(&TypeId::of::<Animation>()) else {
+            return;
+        };
+
+        let delta_time = components
+            .get(&TypeId::of::<GameState>())
+            .and_then(|states| states.values().next())
+            .and_then(|state| state.as_any().downcast_ref::<GameState>())
+            .map_or(0.0, |state| state.delta_time);
+
+        let mut finished = Vec::new();
+        let bodies = components.entry(TypeId::of::<Body>()).or_default();
+
+        for (entity, animation) in animations.iter_mut() {
+            let Some(animation) = animation.as_any_mut().downcast_mut::<Animation>() else {
+                continue;
+            };
+
+            if animation.advance(delta_time) {
+                finished.push(GameEvent::AnimationFinished {
+                    entity: *entity,
+                    clip: animation.current_clip().to_owned(),
+                });
+            }
+            if let Some(body) = animation.take_changed_frame() {
+                bodies.insert(*entity, Box::new(body.clone()));
+            }
+        }
+
+        // Publica os fins de animação para os jogos e os próximos sistemas
+        if let Some(events) = components
+            .get_mut(&TypeId::of::<Events>())
+            .and_then(|events| events.values_mut().next())
+            .and_then(|events| events.as_any_mut().downcast_mut::<Events>())
+        {
+            for event in finished {
+                events.send(event);
+            }
+        }
+
+        components.insert(TypeId::of::<Animation>(), animations);
+    }
+}
+
+pub struct ParticleSystem;
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Simula os emissores de partículas a partir da posição de cada entidade
+impl ISystem for ParticleSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        if is_paused(components) {
+            return;
+        }
+        let Some(mut emitters) = components.remove(&TypeId::of::<ParticleEmitter>()) else {
+            return;
+        };
+
+        let delta_time = components
+            .get(&TypeId::of::<GameState>())
+            .and_then(|states| states.values().next())
+            .and_then(|state| state.as_any().downcast_ref::<GameState>())
+            .map_or(0.0, |state| state.delta_time);
+
+        let mut finished = Vec::new();
+        for (entity, emitter) in emitters.iter_mut() {
+            let Some(emitter) = emitter.as_any_mut().downcast_mut::<ParticleEmitter>() else {
+                continue;
+            };
+            let origin = component::<Position>(components, entity).map_or((0.0, 0.0), |pos| (pos.x, pos.y));
+
+            if emitter.update(origin, delta_time) {
+                finished.push(GameEvent::ParticlesFinished { entity: *entity });
+            }
+        }
+
+        if let Some(events) = components
+            .get_mut(&TypeId::of::<Events>())
+            .and_then(|events| events.values_mut().next())
+            .and_then(|events| events.as_any_mut().downcast_mut::<Events>())
+        {
+            for event in finished {
+                events.send(event);
+            }
+        }
+
+        components.insert(TypeId::of::<ParticleEmitter>(), emitters);
+    }
+}
+
+pub struct ScreenEffectsSystem {
+    effects: ScreenEffects,
+}
+
+impl ScreenEffectsSystem {
+    pub fn new() -> Self {
+        Self { effects: ScreenEffects::new() }
+    }
+}
+
+impl Default for ScreenEffectsSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Aplica os efeitos de tela pedidos via `GameEvent::Effect`; deve rodar depois do `DrawSystem`
+impl ISystem for ScreenEffectsSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        let delta_time = components
+            .get(&TypeId::of::<GameState>())
+            .and_then(|states| states.values().next())
+            .and_then(|state| state.as_any().downcast_ref::<GameState>())
+            .map_or(0.0, |state| state.delta_time);
+
+        let events = components
+            .get_mut(&TypeId::of::<Events>())
+            .and_then(|events| events.values_mut().next())
+            .and_then(|events| events.as_any_mut().downcast_mut::<Events>());
+
+        if let Some(events) = events {
+            for event in events.iter() {
+                if let GameEvent::Effect(effect) = event {
+                    self.effects.trigger(*effect);
+                }
+            }
+            if self.effects.update(delta_time) {
+                let faded_out = self.effects.is_faded_out();
+                events.send(GameEvent::FadeFinished { faded_out });
+            }
+        } else {
+            self.effects.update(delta_time);
+        }
+
+        if let Some(frame) = components
+            .get_mut(&TypeId::of::<FrameBuffer>())
+            .and_then(|frames| frames.values_mut().next())
+            .and_then(|frame| frame.as_any_mut().downcast_mut::<FrameBuffer>())
+        {
+            self.effects.apply(frame);
+        }
+    }
+}
+
+pub struct ScreenshotSystem {
+    key: KeyCode,
+    format: ScreenshotFormat,
+    directory: PathBuf,
+    count: u32,
+}
+
+impl ScreenshotSystem {
+    // Por padrão F12 salva um arquivo .ans no diretório atual
+    pub fn new() -> Self {
+        Self {
+            key: KeyCode::F(12),
+            format: ScreenshotFormat::Ansi,
+            directory: PathBuf::from("."),
+            count: 0,
+        }
+    }
+
+    pub fn with_key(mut self, key: KeyCode) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn with_format(mut self, format: ScreenshotFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    // screenshot-<segundos desde 1970>-<contador>.<extensão>, sem sobrescrever os anteriores
+    fn next_path(&mut self, format: ScreenshotFormat) -> PathBuf {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.count += 1;
+        self.directory
+            .join(format!("screenshot-{}-{}.{}", seconds, self.count, format.extension()))
+    }
+}
+
+impl Default for ScreenshotSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Salva o frame quando a tecla é pressionada ou chega um `GameEvent::Screenshot`;
+// deve ser o último sistema, para o arquivo conter os efeitos de tela
+impl ISystem for ScreenshotSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        let requests: Vec<ScreenshotFormat> = components
+            .get(&TypeId::of::<Events>())
+            .and_then(|events| events.values().next())
+            .and_then(|events| events.as_any().downcast_ref::<Events>())
+            .map(|events| {
+                events
+                    .iter()
+                    .filter_map(|event| match event {
+                        GameEvent::Key(key_event) if key_event.code == self.key => Some(self.format),
+                        GameEvent::Screenshot(format) => Some(*format),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if requests.is_empty() {
+            return;
+        }
+
+        let Some(frame) = components
+            .get(&TypeId::of::<FrameBuffer>())
+            .and_then(|frames| frames.values().next())
+            .and_then(|frame| frame.as_any().downcast_ref::<FrameBuffer>())
+        else {
+            return;
+        };
+        let results: Vec<GameEvent> = requests
+            .into_iter()
+            .map(|format| {
+                let path = self.next_path(format);
+                match screenshot::save_as(frame, &path, format) {
+                    Ok(()) => GameEvent::ScreenshotSaved { path },
+                    Err(error) => GameEvent::ScreenshotFailed { reason: error.to_string() },
+                }
+            })
+            .collect();
+
+        if let Some(events) = components
+            .get_mut(&TypeId::of::<Events>())
+            .and_then(|events| events.values_mut().next())
+            .and_then(|events| events.as_any_mut().downcast_mut::<Events>())
+        {
+            results.into_iter().for_each(|event| events.send(event));
+        }
+    }
+}
+
+pub struct RecordingSystem {
+    key: KeyCode,
+    directory: PathBuf,
+}
+
+impl RecordingSystem {
+    // Por padrão F9 liga e desliga a gravação no diretório atual
+    pub fn new() -> Self {
+        Self {
+            key: KeyCode::F(9),
+            directory: PathBuf::from("."),
+        }
+    }
+
+    pub fn with_key(mut self, key: KeyCode) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = directory.into();
+        self
+    }
+}
+
+impl Default for RecordingSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Liga e desliga a gravação asciicast do `FrameBuffer` pela tecla configurada
+impl ISystem for RecordingSystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        let toggles = components
+            .get(&TypeId::of::<Events>())
+            .and_then(|events| events.values().next())
+            .and_then(|events| events.as_any().downcast_ref::<Events>())
+            .map_or(0, |events| {
+                events
+                    .iter()
+                    .filter(|event| matches!(event, GameEvent::Key(key_event) if key_event.code == self.key))
+                    .count()
+            });
+        if toggles == 0 {
+            return;
+        }
+
+        let Some(frame) = components
+            .get_mut(&TypeId::of::<FrameBuffer>())
+            .and_then(|frames| frames.values_mut().next())
+            .and_then(|frame| frame.as_any_mut().downcast_mut::<FrameBuffer>())
+        else {
+            return;
+        };
+        let mut results = Vec::new();
+        for _ in 0..toggles {
+            let result = match frame.stop_recording() {
+                Some(Ok(path)) => GameEvent::RecordingStopped { path },
+                Some(Err(error)) => GameEvent::RecordingFailed { reason: error.to_string() },
+                None => {
+                    let path = recording::next_path(&self.directory);
+                    match frame.start_recording(&path) {
+                        Ok(()) => GameEvent::RecordingStarted { path },
+                        Err(error) => GameEvent::RecordingFailed { reason: error.to_string() },
+                    }
+                }
+            };
+            results.push(result);
+        }
+
+        if let Some(events) = components
+            .get_mut(&TypeId::of::<Events>())
+            .and_then(|events| events.values_mut().next())
+            .and_then(|events| events.as_any_mut().downcast_mut::<Events>())
+        {
+            results.into_iter().for_each(|event| events.send(event));
+        }
+    }
+}
+
+// Barras do gráfico de tempo de frame, da menor para a maior
+const SPARKLINE: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+// Quantos frames aparecem no gráfico
+const SPARKLINE_WIDTH: usize = 20;
+
+pub struct StatsOverlaySystem {
+    key: KeyCode,
+    pub visible: bool,
+}
+
+impl StatsOverlaySystem {
+    // Por padrão F3 mostra e esconde o overlay
+    pub fn new() -> Self {
+        Self { key: KeyCode::F(3), visible: false }
+    }
+
+    pub fn with_key(mut self, key: KeyCode) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    // Linhas do overlay com a cor de cada célula, limitadas a `width` colunas
+    fn lines(stats: &FrameStats, width: usize) -> Vec<Vec<Cell>> {
+        let text = |content: String, color: Color| -> Vec<Cell> {
+            content.chars().map(|ch| Cell::new(ch).with_fg(color)).collect()
+        };
+        // Verde dentro do FPS pedido, amarelo até o dobro do tempo, vermelho acima
+        let target = stats.target_frame_time.max(f32::EPSILON);
+        let color_of = |seconds: f32| match seconds / target {
+            ratio if ratio <= 1.2 => Color::Green,
+            ratio if ratio <= 2.0 => Color::Yellow,
+            _ => Color::Red,
+        };
+
+        let frame_time = stats.last_frame_time();
+        let mut lines = vec![text(
+            format!("{:.0} fps {:.1}ms", stats.fps(), frame_time * 1000.0),
+            color_of(frame_time),
+        )];
+
+        // Gráfico dos últimos frames; a escala vai até o dobro do tempo pedido ou o maior tempo
+        let shown: Vec<f32> = stats.frame_times.iter().rev().take(width.min(SPARKLINE_WIDTH)).rev().copied().collect();
+        let max = shown.iter().copied().fold(target * 2.0, f32::max);
+        lines.push(
+            shown
+                .iter()
+                .map(|&seconds| {
+                    let level = (seconds / max * (SPARKLINE.len() - 1) as f32).round() as usize;
+                    Cell::new(SPARKLINE[level.min(SPARKLINE.len() - 1)]).with_fg(color_of(seconds))
+                })
+                .collect(),
+        );
+
+        lines.push(text(
+            format!("{} ent {}B", stats.entities, stats.flushed_bytes),
+            Color::White,
+        ));
+
+        // Sistemas mais lentos primeiro
+        let mut systems = stats.systems.clone();
+        systems.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (name, seconds) in systems {
+            lines.push(text(format!("{} {:.2}ms", name, seconds * 1000.0), Color::Grey));
+        }
+
+        for line in lines.iter_mut() {
+            line.truncate(width);
+        }
+        lines
+    }
+}
+
+impl Default for StatsOverlaySystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Mostra FPS, gráfico do tempo de frame, entidades e o tempo de cada sistema no canto da tela;
+// deve rodar depois do `DrawSystem` e dos efeitos de tela
+impl ISystem for StatsOverlaySystem {
+    fn update(&mut self, components: &mut HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>) {
+        let toggles = components
+            .get(&TypeId::of::<Events>())
+            .and_then(|events| events.values().next())
+            .and_then(|events| events.as_any().downcast_ref::<Events>())
+            .map_or(0, |events| {
+                events
+                    .iter()
+                    .filter(|event| matches!(event, GameEvent::Key(key_event) if key_event.code == self.key))
+                    .count()
+            });
+        self.visible ^= toggles % 2 == 1;
+        if !self.visible {
+            return;
+        }
+
+        let Some(mut frames) = components.remove(&TypeId::of::<FrameBuffer>()) else {
+            return;
+        };
+        let stats = components
+            .get(&TypeId::of::<FrameStats>())
+            .and_then(|stats| stats.values().next())
+            .and_then(|stats| stats.as_any().downcast_ref::<FrameStats>());
+
+        if let (Some(frame), Some(stats)) = (
+            frames
+                .values_mut()
+                .next()
+                .and_then(|frame| frame.as_any_mut().downcast_mut::<FrameBuffer>()),
+            stats,
+        ) {
+            let (width, height) = frame.size();
+            let lines = Self::lines(stats, width as usize);
+            let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+
+            // Fundo escuro atrás do texto, para ficar legível sobre qualquer cena
+            for (y, line) in lines.iter().take(height as usize).enumerate() {
+                for x in 0..columns {
+                    let cell = line.get(x).copied().unwrap_or(Cell::BLANK);
+                    frame.set(x as i32, y as i32, cell.with_bg(Color::Black));
+                }
+            }
+        }
+
+        components.insert(TypeId::of::<FrameBuffer>(), frames);
+    }
+}
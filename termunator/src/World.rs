@@ -8,30 +8,40 @@
 // ensuring they communicate and modify the game state correctly.
 // -------------------------------------------------------------------------------------------------------------------------------
 
-use crossterm::{cursor::{Hide, MoveTo, Show}, execute};
+use crossterm::{cursor::{Hide, Show}, execute};
 use crossterm::{
-    event::{self},
-    queue,
+    event::{self, Event},
     terminal::{self},
 };
 
-use std::io::{stdout, Write};
+use std::error::Error;
+use std::io::stdout;
+use std::path::Path;
 
 use std::collections::{HashMap, HashSet};
 use std::any::TypeId;
 use std::thread::sleep;
 use std::time::{Duration, Instant};
 use crate::Entity::Entity;
-use crate::Components::IComponent;
+use crate::Components::{Events, FrameStats, GameEvent, GameState, IComponent};
 use crate::Systems::ISystem;
+use crate::frame_buffer::{FrameBuffer, Layout};
+use crate::capabilities;
+use crate::screenshot;
+use crate::recording;
 
 
 
 pub struct World {
     next_entity_id: u32,
-    systems: HashMap<String, Box<dyn ISystem>>,
+    // Os sistemas rodam na ordem em que foram adicionados
+    systems: Vec<(String, Box<dyn ISystem>)>,
     components: HashMap<TypeId, HashMap<Entity, Box<dyn IComponent>>>,
     entities: HashSet<Entity>,
+    // Indica se a área do jogo já foi posicionada no terminal
+    layout_applied: bool,
+    // Início do frame anterior, para calcular o `delta_time`
+    last_frame: Option<Instant>,
 }
 
 impl World {
@@ -40,9 +50,9 @@ impl World {
             next_entity_id: 0,
             entities: HashSet::new(),
             components: HashMap::new(),
-            systems: HashMap::new(),
-            // Variável para FPS
-
+            systems: Vec::new(),
+            layout_applied: false,
+            last_frame: None,
         }
     }
 
@@ -62,7 +72,9 @@ impl World {
     }
 
     pub fn add_system<S: ISystem + 'static>(&mut self, name: &str, system: S) {
-        self.systems.entry(name.to_owned()).or_insert(Box::new(system));
+        if !self.systems.iter().any(|(existing, _)| existing == name) {
+            self.systems.push((name.to_owned(), Box::new(system)));
+        }
     }
 }
 
@@ -82,6 +94,21 @@ impl World {
             .and_then(|boxed_component| boxed_component.as_any_mut().downcast_mut::<T>()) // Realiza o downcast para &mut T
     }
 
+    // Retorna o primeiro componente do tipo T, útil para recursos únicos como `GameState` e `FrameBuffer`
+    pub fn get_resource<T: IComponent>(&self) -> Option<&T> {
+        self.components
+            .get(&TypeId::of::<T>())
+            .and_then(|entity_map| entity_map.values().next())
+            .and_then(|boxed_component| boxed_component.as_any().downcast_ref::<T>())
+    }
+
+    pub fn get_resource_mut<T: IComponent>(&mut self) -> Option<&mut T> {
+        self.components
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|entity_map| entity_map.values_mut().next())
+            .and_then(|boxed_component| boxed_component.as_any_mut().downcast_mut::<T>())
+    }
+
     pub fn query_components(&self, component_types: &[TypeId]) -> Vec<(Entity, Vec<&dyn IComponent>)> {
         let mut results = Vec::new();
 
@@ -135,7 +162,7 @@ impl World {
     }
 
     pub fn remove_system<S: ISystem + 'static>(&mut self, name: String) {
-        self.systems.remove(&name);
+        self.systems.retain(|(existing, _)| *existing != name);
     }
 
     pub fn delete_entity(&mut self, entity: Entity) {
@@ -153,22 +180,74 @@ impl World {
         let start_time = Instant::now();
         let mut stdout = stdout();
 
-        // Limpeza e atualização - aqui você adiciona a lógica do jogo e o redesenho
-        queue!(
-            stdout,
-            MoveTo(0, 0),
-        ).expect("Erro ao enfileirar comandos");
+        // Tempo real desde o frame anterior, em segundos, usado por animações e efeitos
+        let elapsed = self
+            .last_frame
+            .map_or(frame_duration, |last_frame| start_time - last_frame)
+            .as_secs_f32();
+        self.last_frame = Some(start_time);
+        if let Some(state) = self.get_resource_mut::<GameState>() {
+            state.delta_time = elapsed;
+            state.time += elapsed;
+        }
+
+        // Coleta os eventos do terminal antes dos sistemas rodarem
+        self.poll_events();
+
+        // Centraliza a área do jogo no primeiro frame, com as opções definidas no `GameState`
+        if !self.layout_applied {
+            let previous = self.get_resource::<GameState>().map(|state| state.window_size);
+            let terminal_size = terminal::size().expect("Erro ao obter tamanho do console");
+            let window = self.apply_layout(terminal_size);
+            self.layout_applied = window.is_some();
+
+            // TERMUNATOR_RECORD grava a sessão desde o primeiro frame
+            if self.layout_applied {
+                self.record_from_env();
+            }
 
+            if let Some(window) = window.filter(|window| Some(*window) != previous) {
+                self.send_event(GameEvent::Resize { width: window.0, height: window.1 });
+            }
+        }
 
-        for system in self.systems.values_mut() {
+        // Limpa o back buffer; os sistemas redesenham o frame inteiro
+        if let Some(frame) = self.get_resource_mut::<FrameBuffer>() {
+            frame.clear();
+        }
+
+        // Cada sistema é cronometrado para o `FrameStats`
+        let mut timings = Vec::with_capacity(self.systems.len() + 1);
+        for (name, system) in self.systems.iter_mut() {
+            let started = Instant::now();
             system.update(&mut self.components);
+            timings.push((name.clone(), started.elapsed().as_secs_f32()));
+        }
+        if let Some(events) = self.get_resource_mut::<Events>() {
+            events.mark_delivered();
         }
 
-        stdout.flush().expect("Erro ao atualizar terminal");
+        // Envia apenas as células alteradas para o terminal
+        let started = Instant::now();
+        let mut flushed_bytes = 0;
+        if let Some(frame) = self.get_resource_mut::<FrameBuffer>() {
+            frame.flush(&mut stdout).expect("Erro ao atualizar terminal");
+            flushed_bytes = frame.flushed_bytes();
+        }
+        timings.push(("flush".to_owned(), started.elapsed().as_secs_f32()));
 
-        // Limpa todos os eventos pendentes para evitar o acúmulo de inputs
-        while event::poll(Duration::from_millis(0)).unwrap() {
-            event::read().unwrap(); // Lê e descarta o evento
+        if self.get_resource::<FrameStats>().is_none() {
+            let entity = self.create_entity();
+            self.add_component(entity, FrameStats::new());
+        }
+        let entities = self.entities.len();
+        if let Some(stats) = self.get_resource_mut::<FrameStats>() {
+            stats.push_frame_time(elapsed);
+            stats.target_frame_time = frame_duration.as_secs_f32();
+            stats.work_time = start_time.elapsed().as_secs_f32();
+            stats.systems = timings;
+            stats.flushed_bytes = flushed_bytes;
+            stats.entities = entities;
         }
 
         // Controle de FPS
@@ -178,10 +257,142 @@ impl World {
         }
 
     }
+
+    // Lê todos os eventos pendentes do terminal e os publica no recurso `Events`
+    fn poll_events(&mut self) {
+        if self.get_resource::<Events>().is_none() {
+            let entity = self.create_entity();
+            self.add_component(entity, Events::new());
+        }
+        if let Some(events) = self.get_resource_mut::<Events>() {
+            events.retire();
+        }
+
+        while event::poll(Duration::from_millis(0)).expect("Erro ao verificar input") {
+            match event::read().expect("Erro ao ler evento") {
+                Event::Key(key_event) => self.send_event(GameEvent::Key(key_event)),
+                Event::Resize(cols, rows) => self.resize((cols, rows)),
+                _ => {} // Ignora outros eventos
+            }
+        }
+    }
+
+    // Publica um evento; enviado entre dois `update`s, ele chega aos sistemas no próximo frame
+    pub fn send_event(&mut self, event: GameEvent) {
+        if self.get_resource::<Events>().is_none() {
+            let entity = self.create_entity();
+            self.add_component(entity, Events::new());
+        }
+        if let Some(events) = self.get_resource_mut::<Events>() {
+            events.send(event);
+        }
+    }
+
+    fn record_from_env(&mut self) {
+        let Some(path) = recording::path_from_env() else {
+            return;
+        };
+        let Some(frame) = self.get_resource_mut::<FrameBuffer>() else {
+            return;
+        };
+        let event = match frame.start_recording(&path) {
+            Ok(()) => GameEvent::RecordingStarted { path },
+            Err(error) => GameEvent::RecordingFailed { reason: error.to_string() },
+        };
+        self.send_event(event);
+    }
+
+    // Salva o último frame desenhado; o formato vem da extensão (.txt, .ans ou .html)
+    pub fn save_screenshot<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        let frame = self.get_resource::<FrameBuffer>().ok_or("Nenhum FrameBuffer no mundo")?;
+        screenshot::save(frame, path)
+    }
+
+    // Recalcula a janela para o novo tamanho do terminal e força um redesenho completo
+    fn resize(&mut self, terminal_size: (u16, u16)) {
+        // Uma troca de fonte também chega como resize e muda o tamanho da célula, usado pelos sixels
+        if let Some(frame) = self.get_resource_mut::<FrameBuffer>() {
+            let capabilities = frame.capabilities().clone().with_cell_size(capabilities::detect_cell_size());
+            frame.set_capabilities(capabilities);
+        }
+        if let Some(window) = self.apply_layout(terminal_size) {
+            self.send_event(GameEvent::Resize { width: window.0, height: window.1 });
+        }
+    }
+
+    // Ajusta a janela lógica e o posicionamento do frame buffer ao tamanho do terminal
+    fn apply_layout(&mut self, terminal_size: (u16, u16)) -> Option<(u16, u16)> {
+        let state = self.get_resource_mut::<GameState>()?;
+        let (window, layout) = World::layout(
+            state.requested_size,
+            terminal_size,
+            state.upscale,
+            state.aspect_correction,
+        );
+        state.window_size = window;
+
+        if let Some(frame) = self.get_resource_mut::<FrameBuffer>() {
+            if frame.size() != window {
+                frame.resize(window.0, window.1);
+            }
+            frame.set_layout(layout);
+        }
+
+        Some(window)
+    }
 }
 
 
 impl World {
+    // Reduz o tamanho pedido proporcionalmente até caber no terminal
+    pub fn fit_window(requested: (u16, u16), terminal_size: (u16, u16)) -> (u16, u16) {
+        let (width, height) = requested;
+        let (max_cols, max_rows) = terminal_size;
+        let mut window = (width, height);
+
+        // Verifica se o tamanho informado está dentro dos limites
+        if width > max_cols || height > max_rows {
+            let width_ratio = max_cols as f32 / width as f32;
+            let height_ratio = max_rows as f32 / height as f32;
+            let scale_factor = width_ratio.min(height_ratio);
+            window.0 = (width as f32 * scale_factor) as u16;
+            window.1 = (height as f32 * scale_factor) as u16;
+        }
+
+        window
+    }
+
+    // Calcula a janela lógica e como ela é apresentada no terminal: centralizada, com bordas
+    // de letterbox, ampliada por um fator inteiro (`upscale`) e com cada célula ocupando duas
+    // colunas (`aspect_correction`), já que as células do terminal têm proporção de ~1:2
+    pub fn layout(
+        requested: (u16, u16),
+        terminal_size: (u16, u16),
+        upscale: bool,
+        aspect_correction: bool,
+    ) -> ((u16, u16), Layout) {
+        let (cols, rows) = terminal_size;
+        let (width, height) = (requested.0.max(1), requested.1.max(1));
+        let aspect = if aspect_correction { 2 } else { 1 };
+
+        let factor = if upscale {
+            // Em u32, porque `width * aspect` passa do limite do u16 com janelas muito largas;
+            // o fator nunca é maior que `cols`, então volta para u16 sem perdas
+            (cols as u32 / (width as u32 * aspect as u32)).min((rows / height) as u32).max(1) as u16
+        } else {
+            1
+        };
+        let scale = (aspect * factor, factor);
+
+        let window = World::fit_window(requested, (cols / scale.0, rows / scale.1));
+        let origin = (
+            cols.saturating_sub(window.0 * scale.0) / 2,
+            rows.saturating_sub(window.1 * scale.1) / 2,
+        );
+
+        (window, Layout { origin, scale, terminal_size })
+    }
+
     pub fn init(width: u16, height: u16) -> Result<(u16, u16), Box<dyn std::error::Error>> {
         // Configuração inicial
         terminal::enable_raw_mode().map_err(|e| {
@@ -190,22 +401,13 @@ impl World {
         })?;
     
         // Obtém o tamanho atual do terminal
-        let (max_cols, max_rows) = terminal::size().map_err(|e| {
+        let terminal_size = terminal::size().map_err(|e| {
             eprintln!("Erro ao obter tamanho do console: {:?}", e);
             e
         })?;
-        
-        let mut window = (width, height);
-    
-        // Verifica se o tamanho informado está dentro dos limites
-        if width > max_cols || height > max_rows {
-            let width_ratio = max_cols as f32 / width as f32;
-            let height_ratio = max_rows as f32 / height as f32;
-            let scale_factor = width_ratio.min(height_ratio);
-            window.0 = (width as f32 * scale_factor) as u16;
-            window.1 = (height as f32 * scale_factor) as u16;
-        }
-    
+
+        let window = World::fit_window((width, height), terminal_size);
+
         let mut stdout = stdout();
         execute!(stdout, terminal::EnterAlternateScreen, Hide).map_err(|e| {
             eprintln!("Erro ao configurar terminal alternativo: {:?}", e);
@@ -0,0 +1,193 @@
+// ascii_art.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Converts bitmap images into ASCII art Bodies, to turn reference art into sprites quickly and
+// to show thumbnails in the launcher. The image is averaged down to one sample per cell, the
+// luminance of each sample picks a glyph from a ramp (from empty to dense) and, optionally, the
+// sample color is quantized to the chosen color depth. Ordered or Floyd-Steinberg dithering
+// spreads the rounding error between glyphs, which keeps gradients smooth with short ramps.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use crossterm::style::Color;
+
+use crate::capabilities::{nearest_16, nearest_256, ColorDepth};
+use crate::Components::Body;
+use crate::frame_buffer::Cell;
+use crate::graphics::Image;
+
+// Do mais vazio ao mais denso
+pub const RAMP: &str = " .:-=+*#%@";
+pub const RAMP_BLOCKS: &str = " ░▒▓█";
+
+// Matriz de Bayer 4x4 do pontilhado ordenado
+const BAYER: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dither {
+    None,
+    // Padrão fixo, estável entre quadros de uma animação
+    Ordered,
+    // Difusão do erro para os vizinhos: mais detalhe, mas muda com pequenas alterações
+    FloydSteinberg,
+}
+
+#[derive(Debug, Clone)]
+pub struct AsciiArt {
+    ramp: Vec<char>,
+    pub dither: Dither,
+    // Profundidade das cores das células; `Mono` não colore
+    pub colors: ColorDepth,
+    // Troca claro por escuro, para terminais com fundo claro
+    pub invert: bool,
+}
+
+impl AsciiArt {
+    pub fn new() -> Self {
+        Self {
+            ramp: RAMP.chars().collect(),
+            dither: Dither::None,
+            colors: ColorDepth::Mono,
+            invert: false,
+        }
+    }
+
+    // Caracteres do mais vazio ao mais denso; uma rampa vazia volta à padrão
+    pub fn with_ramp(mut self, ramp: &str) -> Self {
+        self.ramp = ramp.chars().collect();
+        if self.ramp.is_empty() {
+            self.ramp = RAMP.chars().collect();
+        }
+        self
+    }
+
+    pub fn with_dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    pub fn with_colors(mut self, colors: ColorDepth) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn with_invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    // Linhas que mantêm a proporção da imagem com `cols` colunas (células têm ~1:2)
+    pub fn rows_for(image: &Image, cols: u16) -> u16 {
+        let (width, height) = image.size();
+        if width == 0 {
+            return 0;
+        }
+        ((cols as u32 * height) as f32 / width as f32 / 2.0).round().max(1.0) as u16
+    }
+
+    // Converte a imagem em um `body` de `cols` x `rows` células; pixels transparentes
+    // viram células transparentes
+    pub fn convert(&self, image: &Image, cols: u16, rows: u16) -> Body {
+        let (cols, rows) = (cols as usize, rows as usize);
+        let samples: Vec<[f32; 4]> = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| (x, y)))
+            .map(|(x, y)| average(image, x, y, cols, rows))
+            .collect();
+
+        let mut levels: Vec<f32> = samples
+            .iter()
+            .map(|&[r, g, b, _]| {
+                let luminance = (0.2126 * r + 0.7152 * g + 0.0722 * b) / 255.0;
+                if self.invert { 1.0 - luminance } else { luminance }
+            })
+            .collect();
+
+        let steps = (self.ramp.len() - 1).max(1) as f32;
+        let mut mat = vec![vec![Cell::BLANK; cols]; rows];
+        for y in 0..rows {
+            for x in 0..cols {
+                let index = y * cols + x;
+                let level = match self.dither {
+                    Dither::Ordered => levels[index] + (BAYER[y % 4][x % 4] / 16.0 - 0.5) / steps,
+                    _ => levels[index],
+                };
+                let step = (level * steps).round().clamp(0.0, steps);
+
+                if self.dither == Dither::FloydSteinberg {
+                    let error = levels[index] - step / steps;
+                    let mut spread = |dx: isize, dy: usize, weight: f32| {
+                        let nx = x as isize + dx;
+                        if nx >= 0 && (nx as usize) < cols && y + dy < rows {
+                            levels[(y + dy) * cols + nx as usize] += error * weight;
+                        }
+                    };
+                    spread(1, 0, 7.0 / 16.0);
+                    spread(-1, 1, 3.0 / 16.0);
+                    spread(0, 1, 5.0 / 16.0);
+                    spread(1, 1, 1.0 / 16.0);
+                }
+
+                let [r, g, b, alpha] = samples[index];
+                if alpha < 128.0 {
+                    continue;
+                }
+                let ch = self.ramp[step as usize];
+                mat[y][x] = Cell::new(ch).with_fg(self.quantize((r as u8, g as u8, b as u8)));
+            }
+        }
+
+        let body = Body::from_cells(mat);
+        if image.pixels().iter().any(|pixel| pixel[3] < 128) {
+            body.with_transparent(' ')
+        } else {
+            body
+        }
+    }
+
+    // Cor da célula na profundidade escolhida
+    fn quantize(&self, rgb: (u8, u8, u8)) -> Color {
+        match self.colors {
+            ColorDepth::Mono => Color::Reset,
+            ColorDepth::Ansi16 => nearest_16(rgb),
+            ColorDepth::Ansi256 => Color::AnsiValue(nearest_256(rgb)),
+            ColorDepth::TrueColor => Color::Rgb { r: rgb.0, g: rgb.1, b: rgb.2 },
+        }
+    }
+}
+
+impl Default for AsciiArt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Média dos pixels que caem na célula (x, y) de uma grade `cols` x `rows`
+fn average(image: &Image, x: usize, y: usize, cols: usize, rows: usize) -> [f32; 4] {
+    let (width, height) = image.size();
+    let (width, height) = (width as usize, height as usize);
+    let left = x * width / cols;
+    let top = y * height / rows;
+    // Imagens menores que a grade repetem pixels
+    let right = ((x + 1) * width / cols).max(left + 1).min(width);
+    let bottom = ((y + 1) * height / rows).max(top + 1).min(height);
+
+    let mut sum = [0.0; 4];
+    let mut count = 0.0;
+    for py in top..bottom {
+        for px in left..right {
+            let pixel = image.get(px as u32, py as u32);
+            for (total, channel) in sum.iter_mut().zip(pixel) {
+                *total += channel as f32;
+            }
+            count += 1.0;
+        }
+    }
+    if count == 0.0 {
+        return [0.0; 4];
+    }
+    sum.map(|total| total / count)
+}
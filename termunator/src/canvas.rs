@@ -0,0 +1,164 @@
+// canvas.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// A Canvas is a grid of pixels drawn at a higher resolution than the terminal cells.
+// Each cell packs several pixels using special characters: half blocks ('▀', '▄') give
+// 1x2 pixels per cell with independent colors, and Braille patterns give 2x4 dots per cell
+// with a single color. Canvases are rendered into the FrameBuffer like any other Body.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use crossterm::style::Color;
+
+use crate::Components::IComponent;
+use crate::frame_buffer::{Cell, FrameBuffer};
+
+// Ponto aceso dentro de uma célula: (coluna, linha, cor)
+type Dot = (i32, i32, Color);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanvasMode {
+    // 1x2 pixels por célula, cada um com sua cor
+    HalfBlock,
+    // 2x4 pontos por célula, todos com a mesma cor
+    Braille,
+}
+
+impl CanvasMode {
+    // Quantidade de pixels (colunas, linhas) em cada célula do terminal
+    pub fn pixels_per_cell(&self) -> (i32, i32) {
+        match self {
+            CanvasMode::HalfBlock => (1, 2),
+            CanvasMode::Braille => (2, 4),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    pub mode: CanvasMode,
+    width: u16,
+    height: u16,
+    pixels: Vec<Option<Color>>,
+}
+
+impl Canvas {
+    // Cria um canvas com `width` x `height` pixels (não células)
+    pub fn new(width: u16, height: u16, mode: CanvasMode) -> Self {
+        Self {
+            mode,
+            width,
+            height,
+            pixels: vec![None; width as usize * height as usize],
+        }
+    }
+
+    // Cria um canvas que cobre `cols` x `rows` células do terminal
+    pub fn with_cells(cols: u16, rows: u16, mode: CanvasMode) -> Self {
+        let (px, py) = mode.pixels_per_cell();
+        Self::new(cols * px as u16, rows * py as u16, mode)
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    pub fn clear(&mut self) {
+        self.pixels.fill(None);
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(y as usize * self.width as usize + x as usize)
+    }
+
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Color> {
+        self.index(x, y).and_then(|i| self.pixels[i])
+    }
+
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i] = Some(color);
+        }
+    }
+
+    pub fn unset_pixel(&mut self, x: i32, y: i32) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i] = None;
+        }
+    }
+
+    // Desenha o canvas com o canto superior esquerdo em (x, y), em células.
+    // A parte fracionária da posição desloca os pixels dentro das células,
+    // o que deixa o movimento suave em vez de pular uma célula inteira.
+    pub fn render(&self, frame: &mut FrameBuffer, x: f32, y: f32) {
+        let (px, py) = self.mode.pixels_per_cell();
+        let origin_x = (x * px as f32).round() as i32;
+        let origin_y = (y * py as f32).round() as i32;
+
+        // Agrupa os pixels acesos pela célula do terminal em que caem
+        let mut cells: HashMap<(i32, i32), Vec<Dot>> = HashMap::new();
+        for row in 0..self.height as i32 {
+            for col in 0..self.width as i32 {
+                if let Some(color) = self.get_pixel(col, row) {
+                    let (gx, gy) = (origin_x + col, origin_y + row);
+                    cells
+                        .entry((gx.div_euclid(px), gy.div_euclid(py)))
+                        .or_default()
+                        .push((gx.rem_euclid(px), gy.rem_euclid(py), color));
+                }
+            }
+        }
+
+        for ((cell_x, cell_y), dots) in cells {
+            // Preserva o fundo já desenhado embaixo do canvas
+            let below = frame.get(cell_x, cell_y).copied().unwrap_or(Cell::BLANK);
+            let cell = match self.mode {
+                CanvasMode::HalfBlock => half_block_cell(&dots, below.bg),
+                CanvasMode::Braille => braille_cell(&dots, below.bg),
+            };
+            frame.set(cell_x, cell_y, cell);
+        }
+    }
+}
+
+fn half_block_cell(dots: &[Dot], background: Color) -> Cell {
+    let top = dots.iter().find(|(_, y, _)| *y == 0).map(|dot| dot.2);
+    let bottom = dots.iter().find(|(_, y, _)| *y == 1).map(|dot| dot.2);
+
+    match (top, bottom) {
+        (Some(top), Some(bottom)) if top == bottom => Cell::new('█').with_fg(top).with_bg(background),
+        (Some(top), Some(bottom)) => Cell::new('▀').with_fg(top).with_bg(bottom),
+        (Some(top), None) => Cell::new('▀').with_fg(top).with_bg(background),
+        (None, Some(bottom)) => Cell::new('▄').with_fg(bottom).with_bg(background),
+        (None, None) => Cell::BLANK.with_bg(background),
+    }
+}
+
+fn braille_cell(dots: &[Dot], background: Color) -> Cell {
+    // Bits do padrão Braille Unicode para cada ponto (coluna, linha)
+    const BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+    let bits = dots
+        .iter()
+        .fold(0, |bits, (x, y, _)| bits | BITS[*x as usize][*y as usize]);
+    let ch = char::from_u32(0x2800 + bits).unwrap_or(' ');
+
+    // Um caractere só tem uma cor: usa a do primeiro ponto
+    let color = dots.first().map_or(Color::Reset, |dot| dot.2);
+    Cell::new(ch).with_fg(color).with_bg(background)
+}
+
+impl IComponent for Canvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
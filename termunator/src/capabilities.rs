@@ -0,0 +1,450 @@
+// capabilities.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// What the terminal can display. At startup the terminal is queried with a short timeout:
+// XTGETTCAP for truecolor ("RGB"/"Tc"), the kitty graphics probe and DA1, whose sixel attribute
+// also tells whether sixel works (DA1 is answered by every terminal, so it marks the end of the
+// replies). Terminals that don't answer fall back to the environment (TERM, COLORTERM, the
+// locale...). Unicode support always comes from the locale. The FrameBuffer adapts every cell
+// right before writing it: colors are downgraded to the nearest palette entry and glyphs the
+// terminal can't show are substituted (box-drawing becomes '+', '-', '|', and so on). Games
+// keep drawing with full colors and Unicode; TERMUNATOR_COLORS, TERMUNATOR_UNICODE and
+// TERMUNATOR_GRAPHICS override the detection, and TERMUNATOR_QUERY=0 skips the queries.
+// Detection runs once per process, except for the cell size, which changes with the font.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crossterm::style::Color;
+use crossterm::terminal;
+
+use crate::frame_buffer::Cell;
+use crate::graphics::GraphicsProtocol;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    // Sem cores: só atributos (negrito, inverso...)
+    Mono,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    pub color: ColorDepth,
+    pub unicode: bool,
+    pub graphics: GraphicsProtocol,
+    // Tamanho de uma célula em pixels, usado para dimensionar imagens sixel
+    pub cell_size: (u16, u16),
+    // Substituições extras definidas pelo jogo, usadas quando não há Unicode
+    glyphs: HashMap<char, char>,
+}
+
+// Cores da paleta de 16 cores na ordem dos códigos ANSI, com os valores padrão do xterm
+const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+// Tamanho de célula mais comum, usado quando o terminal não informa o seu
+const DEFAULT_CELL_SIZE: (u16, u16) = (10, 20);
+
+// Níveis de cada canal no cubo 6x6x6 da paleta de 256 cores
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl Capabilities {
+    // Tudo suportado: nenhuma conversão é feita
+    pub fn full() -> Self {
+        Self {
+            color: ColorDepth::TrueColor,
+            unicode: true,
+            graphics: GraphicsProtocol::Kitty,
+            cell_size: DEFAULT_CELL_SIZE,
+            glyphs: HashMap::new(),
+        }
+    }
+
+    // Detecta as capacidades do terminal; as consultas só são feitas na primeira chamada
+    pub fn detect() -> Self {
+        static DETECTED: OnceLock<Capabilities> = OnceLock::new();
+        let detected = DETECTED.get_or_init(|| {
+            let replies = query_terminal();
+            Self {
+                color: detect_color_depth(replies),
+                unicode: detect_unicode(),
+                graphics: detect_graphics(replies),
+                cell_size: DEFAULT_CELL_SIZE,
+                glyphs: HashMap::new(),
+            }
+        });
+        // O tamanho da célula muda com a fonte, então é lido de novo a cada chamada
+        detected.clone().with_cell_size(detect_cell_size())
+    }
+
+    pub fn with_color(mut self, color: ColorDepth) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    pub fn with_graphics(mut self, graphics: GraphicsProtocol) -> Self {
+        self.graphics = graphics;
+        self
+    }
+
+    pub fn with_cell_size(mut self, cell_size: (u16, u16)) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    // Substituição de um caractere para terminais sem Unicode; tem prioridade sobre a tabela padrão
+    pub fn with_glyph(mut self, from: char, to: char) -> Self {
+        self.glyphs.insert(from, to);
+        self
+    }
+
+    // Converte a célula para algo que o terminal consegue mostrar
+    pub fn adapt(&self, cell: &Cell) -> Cell {
+        let mut cell = *cell;
+        cell.fg = self.adapt_color(cell.fg);
+        cell.bg = self.adapt_color(cell.bg);
+
+        if !self.unicode && !cell.is_continuation() && (!cell.ch.is_ascii() || cell.extra[0] != '\0') {
+            let ch = self.glyphs.get(&cell.ch).copied().unwrap_or_else(|| ascii_fallback(cell.ch));
+            cell = Cell { fg: cell.fg, bg: cell.bg, attrs: cell.attrs, ..Cell::new(ch) };
+        }
+        cell
+    }
+
+    pub fn adapt_color(&self, color: Color) -> Color {
+        match (self.color, color) {
+            (_, Color::Reset) => Color::Reset,
+            (ColorDepth::TrueColor, color) => color,
+            (ColorDepth::Mono, _) => Color::Reset,
+            (ColorDepth::Ansi256, Color::Rgb { r, g, b }) => Color::AnsiValue(nearest_256((r, g, b))),
+            (ColorDepth::Ansi256, color) => color,
+            (ColorDepth::Ansi16, color) => match to_rgb(color) {
+                Some(rgb) => nearest_16(rgb),
+                None => color,
+            },
+        }
+    }
+}
+
+fn detect_color_depth(replies: Option<Replies>) -> ColorDepth {
+    if let Ok(value) = env::var("TERMUNATOR_COLORS") {
+        match value.to_lowercase().as_str() {
+            "mono" | "none" | "0" => return ColorDepth::Mono,
+            "16" => return ColorDepth::Ansi16,
+            "256" => return ColorDepth::Ansi256,
+            "truecolor" | "24bit" => return ColorDepth::TrueColor,
+            _ => {}
+        }
+    }
+    // https://no-color.org
+    if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return ColorDepth::Mono;
+    }
+    if replies.is_some_and(|replies| replies.truecolor) {
+        return ColorDepth::TrueColor;
+    }
+
+    let colorterm = env::var("COLORTERM").unwrap_or_default().to_lowercase();
+    if colorterm.contains("truecolor") || colorterm.contains("24bit") {
+        return ColorDepth::TrueColor;
+    }
+    // O Windows Terminal e os terminais de IDEs suportam truecolor sem anunciar em COLORTERM
+    if env::var_os("WT_SESSION").is_some() || env::var("TERM_PROGRAM").is_ok_and(|program| program == "vscode") {
+        return ColorDepth::TrueColor;
+    }
+
+    let term = env::var("TERM").unwrap_or_default().to_lowercase();
+    if term == "dumb" {
+        ColorDepth::Mono
+    } else if term.contains("256color") {
+        ColorDepth::Ansi256
+    } else if term.is_empty() && cfg!(windows) {
+        ColorDepth::TrueColor
+    } else {
+        ColorDepth::Ansi16
+    }
+}
+
+fn detect_unicode() -> bool {
+    if let Ok(value) = env::var("TERMUNATOR_UNICODE") {
+        return !matches!(value.as_str(), "0" | "false" | "no");
+    }
+    if cfg!(windows) {
+        return true;
+    }
+    if env::var("TERM").is_ok_and(|term| term == "dumb" || term == "linux") {
+        return false;
+    }
+
+    // A primeira variável de locale definida decide, como na libc
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+        .to_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
+fn detect_graphics(replies: Option<Replies>) -> GraphicsProtocol {
+    if let Ok(value) = env::var("TERMUNATOR_GRAPHICS") {
+        match value.to_lowercase().as_str() {
+            "kitty" => return GraphicsProtocol::Kitty,
+            "sixel" => return GraphicsProtocol::Sixel,
+            "halfblocks" | "blocks" | "none" => return GraphicsProtocol::HalfBlocks,
+            _ => {}
+        }
+    }
+    // Dentro do tmux as sequências de imagem não chegam ao terminal
+    if env::var_os("TMUX").is_some() {
+        return GraphicsProtocol::HalfBlocks;
+    }
+    // Um terminal que respondeu às consultas diz exatamente o que suporta
+    if let Some(replies) = replies {
+        return if replies.kitty {
+            GraphicsProtocol::Kitty
+        } else if replies.sixel {
+            GraphicsProtocol::Sixel
+        } else {
+            GraphicsProtocol::HalfBlocks
+        };
+    }
+
+    let term = env::var("TERM").unwrap_or_default().to_lowercase();
+    let program = env::var("TERM_PROGRAM").unwrap_or_default().to_lowercase();
+    let kitty = env::var_os("KITTY_WINDOW_ID").is_some() || term.contains("kitty") || term.contains("ghostty");
+    if kitty || matches!(program.as_str(), "wezterm" | "ghostty") {
+        GraphicsProtocol::Kitty
+    } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") || program == "mlterm" {
+        GraphicsProtocol::Sixel
+    } else {
+        GraphicsProtocol::HalfBlocks
+    }
+}
+
+// Tamanho da célula em pixels a partir do tamanho da janela informado pelo terminal
+pub fn detect_cell_size() -> (u16, u16) {
+    match terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            ((size.width / size.columns).max(1), (size.height / size.rows).max(1))
+        }
+        _ => DEFAULT_CELL_SIZE,
+    }
+}
+
+// O que o terminal respondeu às consultas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Replies {
+    truecolor: bool,
+    kitty: bool,
+    sixel: bool,
+}
+
+// Quanto esperar pelas respostas; terminais locais respondem em poucos milissegundos
+const QUERY_TIMEOUT: Duration = Duration::from_millis(150);
+
+// Imagem 1x1 do protocolo kitty só para consulta (a=q), XTGETTCAP de "RGB" e "Tc" em
+// hexadecimal e, por último, DA1
+const QUERIES: &[u8] = b"\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\x1bP+q524742\x1b\\\x1bP+q5463\x1b\\\x1b[c";
+
+// Consulta o terminal; `None` quando não há um terminal interativo ou ele não responde a tempo
+fn query_terminal() -> Option<Replies> {
+    if cfg!(test) || !cfg!(unix) || env::var("TERMUNATOR_QUERY").is_ok_and(|value| value == "0") {
+        return None;
+    }
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() || env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return None;
+    }
+
+    // Sem o modo raw as respostas apareceriam na tela e só chegariam depois de um Enter
+    let raw = terminal::is_raw_mode_enabled().unwrap_or(false);
+    if !raw && terminal::enable_raw_mode().is_err() {
+        return None;
+    }
+    let mut stdout = io::stdout();
+    let sent = stdout.write_all(QUERIES).and_then(|_| stdout.flush()).is_ok();
+    let replies = if sent { read_replies(QUERY_TIMEOUT) } else { Vec::new() };
+    if !raw {
+        let _ = terminal::disable_raw_mode();
+    }
+    parse_replies(&replies)
+}
+
+// Lê a entrada até chegar a resposta do DA1 ou acabar o tempo
+#[cfg(unix)]
+fn read_replies(timeout: Duration) -> Vec<u8> {
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    let fd = io::stdin().as_raw_fd();
+    let deadline = Instant::now() + timeout;
+    let mut replies = Vec::new();
+    let mut buffer = [0u8; 256];
+    while parse_replies(&replies).is_none() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let mut poll = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+        // SAFETY: `poll` recebe um único pollfd válido durante a chamada
+        let ready = unsafe { libc::poll(&mut poll, 1, remaining.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            break;
+        }
+        // Lê direto do descritor: o buffer do `Stdin` guardaria teclas que o crossterm não veria
+        // SAFETY: o buffer tem `buffer.len()` bytes graváveis
+        let count = unsafe { libc::read(fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+        if count <= 0 {
+            break;
+        }
+        replies.extend_from_slice(&buffer[..count as usize]);
+    }
+    replies
+}
+
+#[cfg(not(unix))]
+fn read_replies(_timeout: Duration) -> Vec<u8> {
+    Vec::new()
+}
+
+// Interpreta as respostas; sem a do DA1 elas não são conclusivas
+fn parse_replies(bytes: &[u8]) -> Option<Replies> {
+    let text = String::from_utf8_lossy(bytes);
+    // ESC [ ? 62 ; 4 ; 22 c: o atributo 4 é o sixel
+    let start = text.find("\x1b[?")? + 3;
+    let end = start + text[start..].find('c')?;
+    Some(Replies {
+        truecolor: text.contains("\x1bP1+r524742") || text.contains("\x1bP1+r5463"),
+        kitty: text.contains("\x1b_Gi=31;OK"),
+        sixel: text[start..end].split(';').any(|attribute| attribute == "4"),
+    })
+}
+
+// Valor RGB aproximado de uma cor; `None` para `Reset`
+pub(crate) fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(value) => Some(ansi_to_rgb(value)),
+        color => PALETTE.iter().find(|(entry, _)| *entry == color).map(|(_, rgb)| *rgb),
+    }
+}
+
+fn ansi_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => PALETTE[value as usize].1,
+        16..=231 => {
+            let index = value - 16;
+            let level = |i: u8| CUBE_LEVELS[i as usize];
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        _ => {
+            let gray = 8 + (value - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+// Entrada mais próxima da paleta de 256 cores: o cubo 6x6x6 ou a rampa de cinzas
+pub(crate) fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |value: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray = 232 + ((average.saturating_sub(8) + 5) / 10).min(23) as u8;
+
+    if distance(ansi_to_rgb(gray), rgb) < distance(ansi_to_rgb(cube), rgb) {
+        gray
+    } else {
+        cube
+    }
+}
+
+pub(crate) fn nearest_16(rgb: (u8, u8, u8)) -> Color {
+    PALETTE
+        .iter()
+        .min_by_key(|(_, entry)| distance(*entry, rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+// Substituto ASCII para caracteres que terminais sem Unicode não mostram
+fn ascii_fallback(ch: char) -> char {
+    match ch {
+        _ if ch.is_ascii() => ch,
+        '─' | '━' | '═' | '┄' | '┅' | '╌' | '╍' | '▬' => '-',
+        '│' | '┃' | '║' | '┆' | '┇' | '╎' | '╏' => '|',
+        '┊' | '┋' => ':',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' => '+',
+        '╔' | '╗' | '╚' | '╝' | '╠' | '╣' | '╦' | '╩' | '╬' => '+',
+        '╭' | '╮' | '╰' | '╯' | '┏' | '┓' | '┗' | '┛' | '┣' | '┫' | '┳' | '┻' | '╋' => '+',
+        '█' | '▓' | '■' | '▀' | '▄' | '▌' | '▐' => '#',
+        '▁' | '▂' | '▃' => '_',
+        '▅' | '▆' | '▇' => '#',
+        '▒' => '%',
+        '░' => '.',
+        '•' | '·' | '∙' | '°' => '.',
+        '○' | '●' | '◘' | '◙' => 'o',
+        '←' | '◄' => '<',
+        '→' | '►' => '>',
+        '↑' | '▲' => '^',
+        '↓' | '▼' => 'v',
+        '♥' | '♦' | '♣' | '♠' | '☼' | '★' | '☆' => '*',
+        '\u{2800}' => ' ',
+        '\u{2801}'..='\u{28FF}' => '.',
+        _ => '?',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies_need_device_attributes() {
+        assert_eq!(parse_replies(b""), None);
+        assert_eq!(parse_replies(b"\x1bP1+r524742\x1b\\\x1b[?62;4"), None);
+    }
+
+    #[test]
+    fn replies_detect_truecolor_kitty_and_sixel() {
+        let replies = parse_replies(b"\x1b_Gi=31;OK\x1b\\\x1bP1+r524742\x1b\\\x1bP0+r5463\x1b\\\x1b[?62;4;22c");
+        assert_eq!(replies, Some(Replies { truecolor: true, kitty: true, sixel: true }));
+
+        let replies = parse_replies(b"\x1bP0+r524742\x1b\\\x1b[?1;2c");
+        assert_eq!(replies, Some(Replies::default()));
+    }
+}
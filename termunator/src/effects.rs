@@ -0,0 +1,166 @@
+// effects.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Screen effects are post-processing passes over the FrameBuffer, run after everything has been
+// drawn: camera shake with decay, a full-screen color flash, fade to and from black between
+// scenes, and scanline/dim passes (e.g. for pause). Any system or game triggers them by sending
+// `GameEvent::Effect`; the ScreenEffectsSystem keeps their state and, since each pass works on
+// the finished frame, several effects can be active at once.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use crossterm::style::Color;
+
+use crate::capabilities::to_rgb;
+use crate::frame_buffer::{Cell, FrameBuffer};
+use crate::particles::Rng;
+
+// Cores assumidas para `Color::Reset` ao misturar
+const DEFAULT_FG: (u8, u8, u8) = (229, 229, 229);
+const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScreenEffect {
+    // Tremor de até `intensity` células que diminui até parar em `duration` segundos
+    Shake { intensity: f32, duration: f32 },
+    // Cobre a tela com a cor e desaparece em `duration` segundos
+    Flash { color: Color, duration: f32 },
+    // Escurece até o preto e continua assim até um `FadeIn`
+    FadeOut { duration: f32 },
+    FadeIn { duration: f32 },
+    // Escurece uma linha a cada duas
+    Scanlines(bool),
+    // Escurece a tela inteira pela metade
+    Dim(bool),
+    // Interrompe todos os efeitos
+    Clear,
+}
+
+// Estado dos efeitos ativos, mantido pelo `ScreenEffectsSystem`
+#[derive(Debug, Clone)]
+pub struct ScreenEffects {
+    shake: Option<(f32, f32, f32)>,
+    flash: Option<(Color, f32, f32)>,
+    // Nível atual do fade (0 é a tela normal, 1 é preto), alvo e velocidade por segundo
+    fade: (f32, f32, f32),
+    pub scanlines: bool,
+    pub dim: bool,
+    rng: Rng,
+}
+
+impl ScreenEffects {
+    pub fn new() -> Self {
+        Self {
+            shake: None,
+            flash: None,
+            fade: (0.0, 0.0, 0.0),
+            scanlines: false,
+            dim: false,
+            rng: Rng::new(0x9e37_79b9),
+        }
+    }
+
+    pub fn trigger(&mut self, effect: ScreenEffect) {
+        match effect {
+            ScreenEffect::Shake { intensity, duration } => self.shake = Some((intensity, duration, duration)),
+            ScreenEffect::Flash { color, duration } => self.flash = Some((color, duration, duration)),
+            ScreenEffect::FadeOut { duration } => self.fade = (self.fade.0, 1.0, 1.0 / duration.max(f32::EPSILON)),
+            ScreenEffect::FadeIn { duration } => self.fade = (self.fade.0, 0.0, 1.0 / duration.max(f32::EPSILON)),
+            ScreenEffect::Scanlines(enabled) => self.scanlines = enabled,
+            ScreenEffect::Dim(enabled) => self.dim = enabled,
+            ScreenEffect::Clear => *self = Self { rng: self.rng.clone(), ..Self::new() },
+        }
+    }
+
+    // Tela completamente preta depois de um `FadeOut`
+    pub fn is_faded_out(&self) -> bool {
+        self.fade.0 >= 1.0
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.fade.0 != self.fade.1
+    }
+
+    // Avança os efeitos; retorna `true` no passo em que um fade chega ao fim
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        self.shake = self
+            .shake
+            .map(|(intensity, remaining, duration)| (intensity, remaining - delta_time, duration))
+            .filter(|(_, remaining, _)| *remaining > 0.0);
+        self.flash = self
+            .flash
+            .map(|(color, remaining, duration)| (color, remaining - delta_time, duration))
+            .filter(|(_, remaining, _)| *remaining > 0.0);
+
+        let (level, target, speed) = self.fade;
+        let step = speed * delta_time;
+        let next = if level < target { (level + step).min(target) } else { (level - step).max(target) };
+        self.fade = (next, target, speed);
+        level != target && next == target
+    }
+
+    // Aplica os efeitos ativos ao frame já desenhado
+    pub fn apply(&mut self, frame: &mut FrameBuffer) {
+        if let Some((intensity, remaining, duration)) = self.shake {
+            // O deslocamento máximo cai linearmente até zero
+            let amplitude = intensity * remaining / duration;
+            let dx = (self.rng.range(-1.0, 1.0) * amplitude).round() as i32;
+            let dy = (self.rng.range(-1.0, 1.0) * amplitude * 0.5).round() as i32;
+            frame.shift(dx, dy);
+        }
+
+        if self.scanlines {
+            let (width, height) = frame.size();
+            for y in (1..height as i32).step_by(2) {
+                for x in 0..width as i32 {
+                    if let Some(cell) = frame.get_mut(x, y) {
+                        *cell = darken(*cell, 0.5);
+                    }
+                }
+            }
+        }
+
+        let mut darkness = self.fade.0;
+        if self.dim {
+            darkness = 1.0 - (1.0 - darkness) * 0.5;
+        }
+        if darkness > 0.0 {
+            frame.cells_mut().for_each(|cell| *cell = darken(*cell, darkness));
+        }
+
+        if let Some((color, remaining, duration)) = self.flash {
+            let strength = remaining / duration;
+            let Some(rgb) = to_rgb(color) else {
+                return;
+            };
+            frame.cells_mut().for_each(|cell| {
+                cell.fg = mix(cell.fg, DEFAULT_FG, rgb, strength);
+                cell.bg = mix(cell.bg, DEFAULT_BG, rgb, strength);
+            });
+        }
+    }
+}
+
+impl Default for ScreenEffects {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Mistura a cor com `target` na proporção `amount` (0 mantém a cor, 1 vira `target`)
+fn mix(color: Color, default: (u8, u8, u8), target: (u8, u8, u8), amount: f32) -> Color {
+    let (r, g, b) = to_rgb(color).unwrap_or(default);
+    let channel = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount.clamp(0.0, 1.0)).round() as u8;
+    Color::Rgb {
+        r: channel(r, target.0),
+        g: channel(g, target.1),
+        b: channel(b, target.2),
+    }
+}
+
+fn darken(cell: Cell, amount: f32) -> Cell {
+    Cell {
+        fg: mix(cell.fg, DEFAULT_FG, (0, 0, 0), amount),
+        bg: mix(cell.bg, DEFAULT_BG, (0, 0, 0), amount),
+        ..cell
+    }
+}
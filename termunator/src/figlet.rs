@@ -0,0 +1,311 @@
+// figlet.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// FIGlet fonts (.flf) render strings as big letters made of characters, for title screens,
+// banners and "GAME OVER" messages. This module parses standard FIGlet font files, lays
+// characters out with the font's full-width, fitting or smushing rules, and outputs either
+// plain lines, a Body or cells drawn directly into the FrameBuffer. Two fonts are bundled.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::Components::Body;
+use crate::frame_buffer::{Cell, FrameBuffer};
+
+// Caracteres alemães que todo arquivo .flf traz depois do ASCII imprimível
+const DEUTSCH: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+// Regras de smushing horizontal (bits do campo "full layout")
+const SMUSH_EQUAL: i32 = 1;
+const SMUSH_LOWLINE: i32 = 2;
+const SMUSH_HIERARCHY: i32 = 4;
+const SMUSH_PAIR: i32 = 8;
+const SMUSH_BIGX: i32 = 16;
+const SMUSH_HARDBLANK: i32 = 32;
+const LAYOUT_FITTING: i32 = 64;
+const LAYOUT_SMUSHING: i32 = 128;
+
+pub struct FigletFont {
+    pub height: usize,
+    hardblank: char,
+    layout: i32,
+    right_to_left: bool,
+    chars: HashMap<char, Vec<Vec<char>>>,
+}
+
+impl FigletFont {
+    // Fonte de blocos cheios, 5 linhas de altura
+    pub fn block() -> Self {
+        Self::parse(include_str!("../fonts/block.flf")).expect("Fonte block.flf inválida")
+    }
+
+    // Fonte compacta de meio-blocos, 3 linhas de altura
+    pub fn small() -> Self {
+        Self::parse(include_str!("../fonts/small.flf")).expect("Fonte small.flf inválida")
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = content.lines();
+        let header = lines.next().ok_or("Arquivo de fonte vazio")?;
+
+        // flf2a<hardblank> altura baseline largura_max old_layout linhas_de_comentário [direção full_layout ...]
+        let signature = header.get(..5).ok_or("Cabeçalho de fonte inválido")?;
+        if signature != "flf2a" {
+            return Err(format!("Assinatura de fonte desconhecida: {}", signature).into());
+        }
+        let hardblank = header[5..].chars().next().ok_or("Cabeçalho sem hardblank")?;
+        let fields: Vec<i32> = header[5 + hardblank.len_utf8()..]
+            .split_whitespace()
+            .map(|field| field.parse::<i32>())
+            .collect::<Result<_, _>>()?;
+        if fields.len() < 5 {
+            return Err("Cabeçalho de fonte incompleto".into());
+        }
+
+        let height = fields[0].max(1) as usize;
+        let old_layout = fields[3];
+        let comment_lines = fields[4].max(0) as usize;
+        let right_to_left = fields.get(5).is_some_and(|direction| *direction == 1);
+        let layout = match fields.get(6) {
+            Some(full_layout) => *full_layout,
+            None if old_layout < 0 => 0,
+            None if old_layout == 0 => LAYOUT_FITTING,
+            None => LAYOUT_SMUSHING | (old_layout & 63),
+        };
+
+        let mut lines = lines.skip(comment_lines);
+        let mut chars = HashMap::new();
+
+        // Caracteres obrigatórios: ASCII 32..=126 e os 7 alemães (podem faltar em fontes antigas)
+        let required = (32..=126).chain(DEUTSCH);
+        for code in required {
+            match read_char(&mut lines, height) {
+                Some(rows) => {
+                    chars.insert(char::from_u32(code).unwrap_or(' '), rows);
+                }
+                None if code > 126 => break,
+                None => return Err(format!("Fonte incompleta no caractere {}", code).into()),
+            }
+        }
+
+        // Caracteres extras identificados por código ("196  LATIN CAPITAL LETTER A...")
+        while let Some(tag) = lines.next() {
+            let Some(code) = tag.split_whitespace().next().and_then(parse_code) else {
+                continue;
+            };
+            let Some(rows) = read_char(&mut lines, height) else {
+                break;
+            };
+            if let Some(ch) = u32::try_from(code).ok().and_then(char::from_u32) {
+                chars.insert(ch, rows);
+            }
+        }
+
+        Ok(Self {
+            height,
+            hardblank,
+            layout,
+            right_to_left,
+            chars,
+        })
+    }
+
+    // Renderiza o texto em linhas; quebras de linha no texto empilham blocos de letras
+    pub fn render(&self, text: &str) -> Vec<String> {
+        text.lines()
+            .flat_map(|line| self.render_line(line))
+            .map(|row| row.into_iter().map(|ch| if ch == self.hardblank { ' ' } else { ch }).collect())
+            .collect()
+    }
+
+    fn render_line(&self, text: &str) -> Vec<Vec<char>> {
+        let mut output: Vec<Vec<char>> = vec![Vec::new(); self.height];
+        let mut previous_width = 0;
+
+        let glyphs: Vec<char> = if self.right_to_left {
+            text.chars().rev().collect()
+        } else {
+            text.chars().collect()
+        };
+
+        for ch in glyphs {
+            let Some(glyph) = self.chars.get(&ch).or_else(|| self.chars.get(&' ')) else {
+                continue;
+            };
+            let width = glyph.iter().map(Vec::len).max().unwrap_or(0);
+            let overlap = self.overlap(&output, glyph, previous_width, width);
+
+            for (row, glyph_row) in output.iter_mut().zip(glyph) {
+                let start = row.len().saturating_sub(overlap);
+                for (offset, &ch) in glyph_row.iter().enumerate() {
+                    let column = start + offset;
+                    if column < row.len() {
+                        let smushed = self.smush(row[column], ch, previous_width, width);
+                        row[column] = smushed.unwrap_or(ch);
+                    } else {
+                        row.push(ch);
+                    }
+                }
+            }
+            previous_width = width;
+        }
+        output
+    }
+
+    // Quantas colunas o próximo caractere pode sobrepor à saída atual
+    fn overlap(&self, output: &[Vec<char>], glyph: &[Vec<char>], previous_width: usize, width: usize) -> usize {
+        if self.layout & (LAYOUT_FITTING | LAYOUT_SMUSHING) == 0 || output.iter().all(Vec::is_empty) {
+            return 0;
+        }
+
+        let mut max_overlap = width;
+        for (row, glyph_row) in output.iter().zip(glyph) {
+            // Última coluna não vazia da saída e primeira coluna não vazia do caractere
+            let line_end = row.iter().rposition(|ch| *ch != ' ');
+            let char_start = glyph_row.iter().position(|ch| *ch != ' ').unwrap_or(glyph_row.len());
+
+            let mut amount = match line_end {
+                Some(end) => char_start + row.len() - 1 - end,
+                None => char_start + row.len(),
+            };
+            if let (Some(end), Some(&right)) = (line_end, glyph_row.get(char_start)) {
+                if self.smush(row[end], right, previous_width, width).is_some() {
+                    amount += 1;
+                }
+            }
+            max_overlap = max_overlap.min(amount);
+        }
+        max_overlap
+    }
+
+    // Junta dois caracteres sobrepostos segundo as regras da fonte; `None` se não podem se juntar
+    fn smush(&self, left: char, right: char, previous_width: usize, width: usize) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+        if previous_width < 2 || width < 2 || self.layout & LAYOUT_SMUSHING == 0 {
+            return None;
+        }
+
+        let hardblank = self.hardblank;
+        let rules = self.layout & 63;
+
+        // Smushing universal: o caractere da direita vence
+        if rules == 0 {
+            if left == hardblank {
+                return Some(right);
+            }
+            if right == hardblank {
+                return Some(left);
+            }
+            return Some(if self.right_to_left { left } else { right });
+        }
+
+        if rules & SMUSH_HARDBLANK != 0 && left == hardblank && right == hardblank {
+            return Some(left);
+        }
+        if left == hardblank || right == hardblank {
+            return None;
+        }
+        if rules & SMUSH_EQUAL != 0 && left == right {
+            return Some(left);
+        }
+        if rules & SMUSH_LOWLINE != 0 {
+            const BORDERS: &str = "|/\\[]{}()<>";
+            if left == '_' && BORDERS.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && BORDERS.contains(left) {
+                return Some(left);
+            }
+        }
+        if rules & SMUSH_HIERARCHY != 0 {
+            const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |ch: char| CLASSES.iter().position(|class| class.contains(ch));
+            if let (Some(left_class), Some(right_class)) = (class(left), class(right)) {
+                if left_class < right_class {
+                    return Some(right);
+                }
+                if right_class < left_class {
+                    return Some(left);
+                }
+            }
+        }
+        if rules & SMUSH_PAIR != 0 {
+            let pair: String = [left, right].iter().collect();
+            if ["[]", "][", "{}", "}{", "()", ")("].contains(&pair.as_str()) {
+                return Some('|');
+            }
+        }
+        if rules & SMUSH_BIGX != 0 {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Cria um `Body` com o texto; os espaços ficam transparentes
+    pub fn to_body(&self, text: &str) -> Body {
+        let lines = self.render(text);
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        Body::from_lines(&lines).with_transparent(' ')
+    }
+
+    // Desenha o texto direto no frame buffer usando as cores e atributos de `style`
+    pub fn draw(&self, frame: &mut FrameBuffer, x: i32, y: i32, text: &str, style: Cell) {
+        for (row, line) in self.render(text).iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if ch != ' ' {
+                    let cell = Cell { fg: style.fg, bg: style.bg, attrs: style.attrs, ..Cell::new(ch) };
+                    frame.set(x + col as i32, y + row as i32, cell);
+                }
+            }
+        }
+    }
+}
+
+// Lê as `height` linhas de um caractere, removendo os marcadores de fim de linha
+fn read_char<'a, I: Iterator<Item = &'a str>>(lines: &mut I, height: usize) -> Option<Vec<Vec<char>>> {
+    let mut rows = Vec::with_capacity(height);
+    for _ in 0..height {
+        let line = lines.next()?.trim_end();
+        let endmark = line.chars().last();
+        let row = match endmark {
+            Some(mark) => line.trim_end_matches(mark),
+            None => line,
+        };
+        rows.push(row.chars().collect());
+    }
+    Some(rows)
+}
+
+// Códigos de caracteres extras podem ser decimais, hexadecimais (0x) ou octais (0)
+fn parse_code(code: &str) -> Option<i64> {
+    let (negative, code) = match code.strip_prefix('-') {
+        Some(code) => (true, code),
+        None => (false, code),
+    };
+    let value = if let Some(hex) = code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if code.len() > 1 && code.starts_with('0') {
+        i64::from_str_radix(&code[1..], 8).ok()?
+    } else {
+        code.parse().ok()?
+    };
+    Some(if negative { -value } else { value })
+}
@@ -0,0 +1,767 @@
+// frame_buffer.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// The FrameBuffer is a grid of cells that systems render into during a frame.
+// It is double-buffered: the "back" buffer receives the current frame, while the "front" buffer
+// mirrors what is already on the terminal. On flush, both are compared and only the cells that
+// changed are written, grouped into horizontal runs and sent to the terminal in a single write.
+// A cell holds a whole grapheme; wide glyphs (CJK, emoji) take two columns, the second one
+// being a continuation cell, and the buffer never leaves half of a wide glyph on screen.
+// Bitmap images sent with the kitty or sixel protocols are not cells: they are placed after the
+// cells on flush and only sent again when they change. A sixel owns its area: cells drawn over
+// it are never written, so sprites passing over it do not force the whole image to be resent.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::any::Any;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{
+        Attribute, Attributes, Color, Print, ResetColor, SetAttribute, SetAttributes,
+        SetBackgroundColor, SetForegroundColor,
+    },
+    terminal::{Clear, ClearType},
+};
+use unicode_width::UnicodeWidthChar;
+
+use crate::capabilities::Capabilities;
+use crate::graphics::{half_blocks, GraphicsProtocol, Picture};
+use crate::recording::Recorder;
+use crate::Components::IComponent;
+
+// Quantidade máxima de células inalteradas que podem ser reescritas para unir duas runs.
+// Um `MoveTo` custa cerca de 6 a 8 bytes, então reescrever poucas células é mais barato.
+const MAX_RUN_GAP: usize = 4;
+
+// Sem code points extras no grafema
+const NO_EXTRA: [char; 3] = ['\0'; 3];
+
+// Uma célula: grafema, cores de frente/fundo (16, 256 ou truecolor) e atributos de texto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    // Code points seguintes do grafema (marcas combinantes, ZWJ, seletores de variação)
+    pub extra: [char; 3],
+    pub fg: Color,
+    pub bg: Color,
+    pub attrs: Attributes,
+}
+
+impl Cell {
+    pub const BLANK: Cell = Cell {
+        ch: ' ',
+        extra: NO_EXTRA,
+        fg: Color::Reset,
+        bg: Color::Reset,
+        attrs: Attributes::none(),
+    };
+
+    // Segunda coluna de um glifo largo; não é impressa
+    pub const CONTINUATION: Cell = Cell { ch: '\0', ..Cell::BLANK };
+
+    pub fn new(ch: char) -> Self {
+        Self { ch, ..Self::BLANK }
+    }
+
+    // Célula com um grafema inteiro; code points além de 4 são descartados
+    pub fn grapheme(grapheme: &str) -> Self {
+        let mut chars = grapheme.chars();
+        let mut cell = Self::new(chars.next().unwrap_or(' '));
+        for (slot, ch) in cell.extra.iter_mut().zip(chars) {
+            *slot = ch;
+        }
+        cell
+    }
+
+    pub fn is_continuation(&self) -> bool {
+        self.ch == '\0'
+    }
+
+    // Quantas colunas o grafema ocupa no terminal: 1 ou 2 (0 para a continuação)
+    pub fn width(&self) -> u16 {
+        if self.is_continuation() {
+            return 0;
+        }
+        // Emoji com seletor de apresentação, sequências ZWJ e bandeiras ocupam duas colunas
+        let regional = |ch: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&ch);
+        let wide_sequence = self.extra.iter().any(|&ch| ch == '\u{FE0F}' || ch == '\u{200D}')
+            || (regional(self.ch) && regional(self.extra[0]));
+        if wide_sequence {
+            return 2;
+        }
+        self.ch.width().unwrap_or(1).clamp(1, 2) as u16
+    }
+
+    // O grafema pronto para imprimir
+    pub fn symbol(&self) -> impl fmt::Display + '_ {
+        Symbol(self)
+    }
+
+    pub fn with_fg(mut self, fg: Color) -> Self {
+        self.fg = fg;
+        self
+    }
+
+    pub fn with_bg(mut self, bg: Color) -> Self {
+        self.bg = bg;
+        self
+    }
+
+    pub fn with_attr(mut self, attr: Attribute) -> Self {
+        self.attrs.set(attr);
+        self
+    }
+
+    fn style(&self) -> (Color, Color, Attributes) {
+        (self.fg, self.bg, self.attrs)
+    }
+}
+
+struct Symbol<'a>(&'a Cell);
+
+impl fmt::Display for Symbol<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cell = self.0;
+        if cell.is_continuation() {
+            return Ok(());
+        }
+        write!(f, "{}", cell.ch)?;
+        for &ch in cell.extra.iter().take_while(|&&ch| ch != '\0') {
+            write!(f, "{}", ch)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::BLANK
+    }
+}
+
+// Retângulo em coordenadas de tela
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: u16, height: u16) -> Self {
+        Self { x, y, width, height }
+    }
+
+    pub fn right(&self) -> i32 {
+        self.x + self.width as i32
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height as i32
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.right() && y < self.bottom()
+    }
+
+    // Retângulo reduzido em `amount` células de cada lado
+    pub fn shrink(&self, amount: u16) -> Rect {
+        Rect {
+            x: self.x + amount as i32,
+            y: self.y + amount as i32,
+            width: self.width.saturating_sub(amount * 2),
+            height: self.height.saturating_sub(amount * 2),
+        }
+    }
+}
+
+// Como o frame buffer é apresentado no terminal: a célula (x, y) do buffer ocupa
+// `scale.0` colunas por `scale.1` linhas a partir de `origin`; o resto é letterbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub origin: (u16, u16),
+    pub scale: (u16, u16),
+    pub terminal_size: (u16, u16),
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            origin: (0, 0),
+            scale: (1, 1),
+            terminal_size: (0, 0),
+        }
+    }
+}
+
+// Sequência de células alteradas e contíguas de uma mesma linha
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub x: u16,
+    pub y: u16,
+    pub cells: Vec<Cell>,
+}
+
+// Imagem enviada por um protocolo gráfico, em coordenadas absolutas do buffer
+#[derive(Debug, Clone)]
+struct Placement {
+    x: u16,
+    y: u16,
+    cols: u16,
+    rows: u16,
+    bytes: Rc<[u8]>,
+}
+
+impl Placement {
+    // Partes da run fora da área da imagem: um sixel é apagado por qualquer célula escrita por
+    // cima, então a área dele é exclusiva
+    fn clip(&self, run: Run) -> Vec<Run> {
+        let (left, right) = (self.x as usize, self.x as usize + self.cols as usize);
+        let (start, end) = (run.x as usize, run.x as usize + run.cells.len());
+        let inside_rows = run.y >= self.y && (run.y as usize) < self.y as usize + self.rows as usize;
+        if !inside_rows || end <= left || start >= right {
+            return vec![run];
+        }
+
+        let mut parts = Vec::new();
+        if start < left {
+            let mut cells = run.cells[..left - start].to_vec();
+            // Um glifo largo cortado invadiria a imagem com a segunda coluna
+            if let Some(last) = cells.last_mut().filter(|cell| cell.width() > 1) {
+                *last = Cell { ch: ' ', extra: NO_EXTRA, ..*last };
+            }
+            parts.push(Run { x: run.x, y: run.y, cells });
+        }
+        if end > right {
+            let mut cells = run.cells[right - start..].to_vec();
+            if let Some(first) = cells.first_mut().filter(|cell| cell.is_continuation()) {
+                *first = Cell { ch: ' ', ..*first };
+            }
+            parts.push(Run { x: right as u16, y: run.y, cells });
+        }
+        parts
+    }
+}
+
+impl PartialEq for Placement {
+    fn eq(&self, other: &Self) -> bool {
+        (self.x, self.y, self.cols, self.rows) == (other.x, other.y, other.cols, other.rows)
+            && Rc::ptr_eq(&self.bytes, &other.bytes)
+    }
+}
+
+pub struct FrameBuffer {
+    width: u16,
+    height: u16,
+    back: Vec<Cell>,
+    front: Vec<Cell>,
+    full_redraw: bool,
+    // Visão atual: as coordenadas passam a ser relativas a ela e tudo fora é recortado
+    view: Option<Rect>,
+    layout: Layout,
+    // Célula usada para preencher as bordas fora da área do jogo
+    letterbox: Cell,
+    // Cores e caracteres que o terminal suporta; as células são adaptadas no flush
+    capabilities: Capabilities,
+    // Gravação em andamento: cada flush vira um evento do arquivo .cast
+    recorder: Option<Recorder>,
+    // Imagens do frame atual e as que já estão no terminal
+    placements: Vec<Placement>,
+    shown_placements: Vec<Placement>,
+    // Tamanho da última escrita no terminal
+    flushed_bytes: usize,
+}
+
+impl FrameBuffer {
+    pub fn new(width: u16, height: u16) -> Self {
+        let len = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            back: vec![Cell::BLANK; len],
+            front: vec![Cell::BLANK; len],
+            full_redraw: true,
+            view: None,
+            layout: Layout::default(),
+            letterbox: Cell::BLANK,
+            capabilities: Capabilities::detect(),
+            recorder: None,
+            placements: Vec::new(),
+            shown_placements: Vec::new(),
+            flushed_bytes: 0,
+        }
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    // Limpa o back buffer para o próximo frame (o front continua igual ao terminal)
+    pub fn clear(&mut self) {
+        self.back.fill(Cell::BLANK);
+        self.placements.clear();
+    }
+
+    // Redimensiona os buffers; o próximo flush redesenha a tela inteira
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let len = width as usize * height as usize;
+        self.width = width;
+        self.height = height;
+        self.back = vec![Cell::BLANK; len];
+        self.front = vec![Cell::BLANK; len];
+        self.full_redraw = true;
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: Layout) {
+        if layout.terminal_size != self.layout.terminal_size {
+            let (cols, rows) = layout.terminal_size;
+            self.record(|recorder| recorder.resize(cols, rows));
+        }
+        self.layout = layout;
+        self.full_redraw = true;
+    }
+
+    pub fn set_letterbox(&mut self, cell: Cell) {
+        self.letterbox = cell;
+        self.full_redraw = true;
+    }
+
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = capabilities;
+        self.full_redraw = true;
+    }
+
+    // Bytes enviados ao terminal no último flush (útil para medir o custo em conexões lentas)
+    pub fn flushed_bytes(&self) -> usize {
+        self.flushed_bytes
+    }
+
+    // Começa a gravar em um arquivo asciicast; o próximo flush redesenha a tela inteira
+    pub fn start_recording<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let (cols, rows) = match self.layout.terminal_size {
+            (0, _) | (_, 0) => self.size(),
+            size => size,
+        };
+        self.recorder = Some(Recorder::create(path, cols, rows)?);
+        self.full_redraw = true;
+        Ok(())
+    }
+
+    // Termina a gravação e retorna o caminho do arquivo
+    pub fn stop_recording(&mut self) -> Option<io::Result<PathBuf>> {
+        self.recorder.take().map(Recorder::finish)
+    }
+
+    pub fn recording(&self) -> Option<&Path> {
+        self.recorder.as_ref().map(Recorder::path)
+    }
+
+    // Um erro ao gravar interrompe só a gravação, não o jogo
+    fn record(&mut self, write: impl FnOnce(&mut Recorder) -> io::Result<()>) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            if write(recorder).is_err() {
+                self.recorder = None;
+            }
+        }
+    }
+
+    // Converte uma posição do terminal (ex: mouse) para a célula correspondente do buffer
+    pub fn terminal_to_buffer(&self, col: u16, row: u16) -> Option<(u16, u16)> {
+        let Layout { origin, scale, .. } = self.layout;
+        let x = col.checked_sub(origin.0)? / scale.0;
+        let y = row.checked_sub(origin.1)? / scale.1;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    // Força o próximo flush a limpar o terminal e reescrever todas as células
+    pub fn invalidate(&mut self) {
+        self.full_redraw = true;
+    }
+
+    // Restringe o desenho a uma sub-área da tela (viewports); `None` volta à tela inteira
+    pub fn set_view(&mut self, view: Option<Rect>) {
+        self.view = view;
+    }
+
+    // Tamanho da visão atual
+    pub fn view_size(&self) -> (u16, u16) {
+        self.view.map_or(self.size(), |view| (view.width, view.height))
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let (x, y) = match self.view {
+            Some(view) => {
+                let (x, y) = (x + view.x, y + view.y);
+                if !view.contains(x, y) {
+                    return None;
+                }
+                (x, y)
+            }
+            None => (x, y),
+        };
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(y as usize * self.width as usize + x as usize)
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&Cell> {
+        self.index(x, y).map(|i| &self.back[i])
+    }
+
+    pub fn get_mut(&mut self, x: i32, y: i32) -> Option<&mut Cell> {
+        self.index(x, y).map(move |i| &mut self.back[i])
+    }
+
+    // Escreve uma célula no back buffer, ignorando posições fora da tela.
+    // Um glifo largo também ocupa a célula da direita; se ela fica fora da tela ou da
+    // visão, o glifo vira um espaço para nunca aparecer cortado pela metade.
+    pub fn set(&mut self, x: i32, y: i32, cell: Cell) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        // Continuações só são escritas junto com o glifo largo
+        if cell.is_continuation() {
+            return;
+        }
+
+        self.split_wide(i);
+        if cell.width() < 2 {
+            self.back[i] = cell;
+            return;
+        }
+        match self.index(x + 1, y) {
+            Some(next) => {
+                self.split_wide(next);
+                self.back[i] = cell;
+                self.back[next] = Cell { fg: cell.fg, bg: cell.bg, attrs: cell.attrs, ..Cell::CONTINUATION };
+            }
+            None => self.back[i] = Cell { fg: cell.fg, bg: cell.bg, attrs: cell.attrs, ..Cell::BLANK },
+        }
+    }
+
+    // Antes de sobrescrever a célula `i`, troca por espaço a outra metade do glifo largo que ela integra
+    fn split_wide(&mut self, i: usize) {
+        let cell = self.back[i];
+        let other = if cell.is_continuation() {
+            i.checked_sub(1)
+        } else if cell.width() == 2 {
+            Some(i + 1).filter(|&next| next < self.back.len())
+        } else {
+            None
+        };
+        if let Some(other) = other {
+            let half = self.back[other];
+            self.back[other] = Cell { fg: half.fg, bg: half.bg, attrs: half.attrs, ..Cell::BLANK };
+        }
+    }
+
+    // Todas as células do back buffer, linha a linha, ignorando a visão atual
+    pub fn cells(&self) -> &[Cell] {
+        &self.back
+    }
+
+    // Todas as células do back buffer, ignorando a visão atual (usado por efeitos de tela)
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut Cell> {
+        self.back.iter_mut()
+    }
+
+    // Desloca o frame inteiro; as células que entram pelas bordas ficam vazias
+    pub fn shift(&mut self, dx: i32, dy: i32) {
+        if (dx == 0 && dy == 0) || self.back.is_empty() {
+            return;
+        }
+        let (width, height) = (self.width as i32, self.height as i32);
+        let source = self.back.clone();
+        for y in 0..height {
+            for x in 0..width {
+                let (from_x, from_y) = (x - dx, y - dy);
+                let inside = from_x >= 0 && from_y >= 0 && from_x < width && from_y < height;
+                self.back[(y * width + x) as usize] = if inside {
+                    source[(from_y * width + from_x) as usize]
+                } else {
+                    Cell::BLANK
+                };
+            }
+        }
+
+        // Glifos largos cortados pelas bordas viram espaços
+        for y in 0..height as usize {
+            let row = y * width as usize;
+            let (first, last) = (row, row + width as usize - 1);
+            if self.back[first].is_continuation() {
+                self.back[first] = Cell::BLANK;
+            }
+            if self.back[last].width() == 2 {
+                self.back[last] = Cell::BLANK;
+            }
+        }
+    }
+
+    // Desenha uma imagem com o protocolo gráfico do terminal. Os protocolos não recortam a
+    // imagem, então uma que não cabe inteira na visão é desenhada com meios-blocos.
+    pub fn draw_picture(&mut self, x: i32, y: i32, picture: &Picture) {
+        let (cols, rows) = picture.size();
+        if cols == 0 || rows == 0 {
+            return;
+        }
+        let protocol = self.capabilities.graphics;
+        let corner = self.index(x, y);
+        let inside = corner.is_some() && self.index(x + cols as i32 - 1, y + rows as i32 - 1).is_some();
+
+        let Some(corner) = corner.filter(|_| inside && protocol != GraphicsProtocol::HalfBlocks) else {
+            let body = half_blocks(&picture.image().resized(cols as u32, rows as u32 * 2));
+            crate::draw_at(&body, x, y, self);
+            return;
+        };
+
+        // O sixel substitui os pixels das células, então o texto embaixo dele é apagado
+        if protocol == GraphicsProtocol::Sixel {
+            for dy in 0..rows as i32 {
+                for dx in 0..cols as i32 {
+                    self.set(x + dx, y + dy, Cell::BLANK);
+                }
+            }
+        }
+
+        let scale = self.layout.scale;
+        let cells = (cols * scale.0, rows * scale.1);
+        let bytes = picture.encoded(protocol, cells, self.capabilities.cell_size);
+        self.placements.push(Placement {
+            x: (corner % self.width as usize) as u16,
+            y: (corner / self.width as usize) as u16,
+            cols,
+            rows,
+            bytes,
+        });
+    }
+
+    pub fn put_char(&mut self, x: i32, y: i32, ch: char) {
+        self.set(x, y, Cell::new(ch));
+    }
+
+    // Escreve um texto grafema a grafema, avançando duas colunas nos glifos largos
+    pub fn put_str(&mut self, x: i32, y: i32, text: &str) {
+        for (offset, cell) in crate::unicode::cells(text).into_iter().enumerate() {
+            self.set(x + offset as i32, y, cell);
+        }
+    }
+
+    // Compara o back buffer com o front e agrupa as células alteradas em runs por linha
+    pub fn diff(&self) -> Vec<Run> {
+        let mut runs = Vec::new();
+        let width = self.width as usize;
+
+        for y in 0..self.height as usize {
+            let row = y * width..(y + 1) * width;
+            let back = &self.back[row.clone()];
+            let front = &self.front[row];
+
+            let mut x = 0;
+            while x < width {
+                if !self.full_redraw && back[x] == front[x] {
+                    x += 1;
+                    continue;
+                }
+
+                // Estende a run enquanto houver mudanças separadas por lacunas pequenas
+                let start = x;
+                let mut end = x + 1;
+                let mut cursor = end;
+                while cursor < width {
+                    if self.full_redraw || back[cursor] != front[cursor] {
+                        end = cursor + 1;
+                    } else if cursor - end >= MAX_RUN_GAP {
+                        break;
+                    }
+                    cursor += 1;
+                }
+
+                // Uma run nunca começa ou termina no meio de um glifo largo
+                let start = if start > 0 && back[start].is_continuation() { start - 1 } else { start };
+                let end = if end < width && back[end].is_continuation() { end + 1 } else { end };
+
+                runs.push(Run {
+                    x: start as u16,
+                    y: y as u16,
+                    cells: back[start..end].to_vec(),
+                });
+                x = end;
+            }
+        }
+        runs
+    }
+
+    // Escreve as runs alteradas em `out` com uma única escrita e um único flush
+    pub fn flush<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let mut bytes: Vec<u8> = Vec::new();
+
+        // Um sixel removido ou movido só sai da tela reescrevendo as células embaixo dele
+        let sixel = self.capabilities.graphics == GraphicsProtocol::Sixel;
+        if sixel && self.shown_placements.iter().any(|shown| !self.placements.contains(shown)) {
+            self.full_redraw = true;
+        }
+
+        if self.full_redraw {
+            queue!(bytes, SetAttribute(Attribute::Reset), ResetColor, Clear(ClearType::All))?;
+            if self.letterbox != Cell::BLANK {
+                self.write_letterbox(&mut bytes)?;
+            }
+        }
+
+        let mut runs = self.diff();
+        if sixel {
+            for placement in &self.placements {
+                runs = runs.into_iter().flat_map(|run| placement.clip(run)).collect();
+            }
+        }
+        let redraw_placements = self.full_redraw || self.placements != self.shown_placements;
+
+        if !runs.is_empty() {
+            // Começa de um estilo conhecido e só emite trocas de cor/atributo quando mudam
+            queue!(bytes, SetAttribute(Attribute::Reset), ResetColor)?;
+            let mut current = Cell::BLANK.style();
+            let Layout { origin, scale, .. } = self.layout;
+
+            for run in runs {
+                // Cada linha do buffer ocupa `scale.1` linhas do terminal
+                for line in 0..scale.1 {
+                    let x = origin.0 + run.x * scale.0;
+                    let y = origin.1 + run.y * scale.1 + line;
+                    queue!(bytes, MoveTo(x, y))?;
+                    for cell in &run.cells {
+                        // A metade direita de um glifo largo já foi ocupada pela impressão dele
+                        if cell.is_continuation() {
+                            continue;
+                        }
+                        let shown = self.capabilities.adapt(cell);
+                        write_style(&mut bytes, &mut current, &shown)?;
+                        for _ in 0..scale.0 {
+                            queue!(bytes, Print(shown.symbol()))?;
+                            // Um glifo largo trocado por um estreito deixa a coluna da continuação vazia
+                            if cell.width() > shown.width() {
+                                queue!(bytes, Print(' '))?;
+                            }
+                        }
+                    }
+                }
+            }
+
+            queue!(bytes, SetAttribute(Attribute::Reset), ResetColor)?;
+        }
+
+        if redraw_placements {
+            self.write_placements(&mut bytes)?;
+        }
+
+        if !bytes.is_empty() {
+            out.write_all(&bytes)?;
+            out.flush()?;
+            self.record(|recorder| recorder.output(&bytes));
+        }
+
+        self.flushed_bytes = bytes.len();
+        self.front.clone_from(&self.back);
+        self.shown_placements.clone_from(&self.placements);
+        self.full_redraw = false;
+        Ok(())
+    }
+}
+
+impl FrameBuffer {
+    // Envia as imagens do frame; no kitty as anteriores são apagadas antes
+    fn write_placements<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        if self.capabilities.graphics == GraphicsProtocol::Kitty && !self.shown_placements.is_empty() {
+            out.write_all(b"\x1b_Ga=d,d=A,q=2\x1b\\")?;
+        }
+        let Layout { origin, scale, .. } = self.layout;
+        for placement in &self.placements {
+            queue!(out, MoveTo(origin.0 + placement.x * scale.0, origin.1 + placement.y * scale.1))?;
+            out.write_all(&placement.bytes)?;
+        }
+        Ok(())
+    }
+
+    // Preenche com a célula de letterbox tudo que fica fora da área do jogo
+    fn write_letterbox<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let Layout { origin, scale, terminal_size } = self.layout;
+        let (cols, rows) = terminal_size;
+        let area = Rect::new(
+            origin.0 as i32,
+            origin.1 as i32,
+            self.width * scale.0,
+            self.height * scale.1,
+        );
+
+        let letterbox = self.capabilities.adapt(&self.letterbox);
+        let mut current = Cell::BLANK.style();
+        for row in 0..rows {
+            // Linhas que cruzam a área do jogo só recebem as barras laterais
+            let spans = if row as i32 >= area.y && (row as i32) < area.bottom() {
+                vec![(0, area.x as u16), (area.right() as u16, cols)]
+            } else {
+                vec![(0, cols)]
+            };
+
+            for (start, end) in spans {
+                if start >= end {
+                    continue;
+                }
+                queue!(out, MoveTo(start, row))?;
+                write_style(out, &mut current, &letterbox)?;
+                for _ in start..end {
+                    queue!(out, Print(letterbox.symbol()))?;
+                }
+            }
+        }
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)
+    }
+}
+
+// Emite apenas os comandos necessários para passar do estilo atual para o da célula
+pub(crate) fn write_style<W: Write>(out: &mut W, current: &mut (Color, Color, Attributes), cell: &Cell) -> io::Result<()> {
+    let (mut fg, mut bg, attrs) = *current;
+
+    if attrs != cell.attrs {
+        // `Attribute::Reset` também limpa as cores, então elas precisam ser reenviadas
+        queue!(out, SetAttribute(Attribute::Reset))?;
+        if !cell.attrs.is_empty() {
+            queue!(out, SetAttributes(cell.attrs))?;
+        }
+        fg = Color::Reset;
+        bg = Color::Reset;
+    }
+    if fg != cell.fg {
+        queue!(out, SetForegroundColor(cell.fg))?;
+    }
+    if bg != cell.bg {
+        queue!(out, SetBackgroundColor(cell.bg))?;
+    }
+
+    *current = cell.style();
+    Ok(())
+}
+
+impl IComponent for FrameBuffer {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,393 @@
+// graphics.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Bitmap images for splash screens and backgrounds. Terminals that implement the kitty graphics
+// protocol or sixel get the real pixels, encoded here in pure Rust (including the base64 of the
+// kitty protocol), so the emitted byte streams are deterministic and easy to compare. Everywhere
+// else the image falls back to half-block characters: each cell shows two pixels, the top one
+// as the foreground of '▀' and the bottom one as the background. A `Picture` component draws
+// an image over a given number of cells; the protocol comes from the terminal Capabilities.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crossterm::style::Color;
+
+use crate::Components::{Body, IComponent};
+use crate::frame_buffer::Cell;
+
+// Pixels com alfa abaixo disso são transparentes (sixel e meios-blocos não têm transparência parcial)
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Tamanho máximo de cada pedaço de dados do protocolo kitty
+const KITTY_CHUNK: usize = 4096;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    // Caracteres '▀' coloridos: funciona em qualquer terminal com cores
+    HalfBlocks,
+    Sixel,
+    Kitty,
+}
+
+// Imagem RGBA, linha a linha a partir do canto superior esquerdo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    // Imagem totalmente transparente
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0, 0, 0, 0]; width as usize * height as usize],
+        }
+    }
+
+    // Pixels a mais são descartados e os que faltam ficam transparentes
+    pub fn from_pixels(width: u32, height: u32, mut pixels: Vec<[u8; 4]>) -> Self {
+        pixels.resize(width as usize * height as usize, [0, 0, 0, 0]);
+        Self { width, height, pixels }
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn pixels(&self) -> &[[u8; 4]] {
+        &self.pixels
+    }
+
+    // Pixel em (x, y); fora da imagem é transparente
+    pub fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        if x >= self.width || y >= self.height {
+            return [0, 0, 0, 0];
+        }
+        self.pixels[y as usize * self.width as usize + x as usize]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, rgba: [u8; 4]) {
+        if x < self.width && y < self.height {
+            self.pixels[y as usize * self.width as usize + x as usize] = rgba;
+        }
+    }
+
+    // Cópia redimensionada pelo vizinho mais próximo, que mantém a pixel art nítida
+    pub fn resized(&self, width: u32, height: u32) -> Image {
+        if (width, height) == self.size() {
+            return self.clone();
+        }
+        let mut image = Image::new(width, height);
+        if self.width == 0 || self.height == 0 {
+            return image;
+        }
+        for y in 0..height {
+            let source_y = (y as u64 * self.height as u64 / height as u64) as u32;
+            for x in 0..width {
+                let source_x = (x as u64 * self.width as u64 / width as u64) as u32;
+                image.set(x, y, self.get(source_x, source_y));
+            }
+        }
+        image
+    }
+}
+
+fn is_opaque(rgba: [u8; 4]) -> bool {
+    rgba[3] >= ALPHA_THRESHOLD
+}
+
+fn rgb(rgba: [u8; 4]) -> Color {
+    Color::Rgb { r: rgba[0], g: rgba[1], b: rgba[2] }
+}
+
+// Converte a imagem em um `Body` com uma célula para cada dois pixels na vertical;
+// células sem nenhum pixel opaco são transparentes
+pub fn half_blocks(image: &Image) -> Body {
+    let (width, height) = image.size();
+    let mat = (0..height.div_ceil(2))
+        .map(|row| {
+            (0..width)
+                .map(|x| {
+                    let top = image.get(x, row * 2);
+                    let bottom = image.get(x, row * 2 + 1);
+                    match (is_opaque(top), is_opaque(bottom)) {
+                        (true, true) => Cell::new('▀').with_fg(rgb(top)).with_bg(rgb(bottom)),
+                        (true, false) => Cell::new('▀').with_fg(rgb(top)),
+                        (false, true) => Cell::new('▄').with_fg(rgb(bottom)),
+                        (false, false) => Cell::BLANK,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    Body::from_cells(mat).with_transparent(' ')
+}
+
+pub fn base64(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let group = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+// Transmite e mostra a imagem esticada sobre `cols` x `rows` células a partir do cursor.
+// A imagem fica embaixo do texto (z=-1), não move o cursor e o terminal não responde.
+pub fn encode_kitty(image: &Image, cols: u16, rows: u16) -> Vec<u8> {
+    let (width, height) = image.size();
+    let data: Vec<u8> = image.pixels().iter().flatten().copied().collect();
+    let payload = base64(&data);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+
+    let mut out = Vec::with_capacity(payload.len() + chunks.len() * 16 + 64);
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = (index + 1 < chunks.len()) as u8;
+        out.extend_from_slice(b"\x1b_G");
+        if index == 0 {
+            let header = format!("a=T,f=32,s={},v={},c={},r={},z=-1,C=1,q=2,", width, height, cols, rows);
+            out.extend_from_slice(header.as_bytes());
+        }
+        out.extend_from_slice(format!("m={};", more).as_bytes());
+        out.extend_from_slice(chunk);
+        out.extend_from_slice(b"\x1b\\");
+    }
+    out
+}
+
+// Índice da cor no cubo 6x6x6 usado como paleta do sixel
+fn sixel_color(rgba: [u8; 4]) -> usize {
+    let level = |value: u8| (value as usize * 5 + 127) / 255;
+    level(rgba[0]) * 36 + level(rgba[1]) * 6 + level(rgba[2])
+}
+
+// Codifica a imagem em sixel, um pixel da imagem por pixel da tela. As cores são reduzidas ao
+// cubo 6x6x6 e pixels transparentes não são pintados (o fundo do terminal continua visível).
+pub fn encode_sixel(image: &Image) -> Vec<u8> {
+    let (width, height) = image.size();
+    let mut out = Vec::new();
+    out.extend_from_slice(format!("\x1bP0;1;0q\"1;1;{};{}", width, height).as_bytes());
+
+    let colors: Vec<Option<usize>> = image
+        .pixels()
+        .iter()
+        .map(|&rgba| is_opaque(rgba).then(|| sixel_color(rgba)))
+        .collect();
+
+    // Define só as cores usadas, em porcentagem de cada canal
+    let mut used = [false; 216];
+    colors.iter().flatten().for_each(|&color| used[color] = true);
+    for color in (0..216).filter(|&color| used[color]) {
+        let percent = |level: usize| level * 100 / 5;
+        let definition = format!("#{};2;{};{};{}", color, percent(color / 36), percent(color / 6 % 6), percent(color % 6));
+        out.extend_from_slice(definition.as_bytes());
+    }
+
+    // Cada faixa tem 6 linhas de pixels; cada cor da faixa é uma passada com o bit de cada linha
+    for band in 0..height.div_ceil(6) {
+        let mut band_colors: Vec<usize> = Vec::new();
+        for y in band * 6..(band * 6 + 6).min(height) {
+            for x in 0..width {
+                if let Some(color) = colors[(y * width + x) as usize] {
+                    if !band_colors.contains(&color) {
+                        band_colors.push(color);
+                    }
+                }
+            }
+        }
+        band_colors.sort_unstable();
+
+        for (pass, &color) in band_colors.iter().enumerate() {
+            if pass > 0 {
+                out.push(b'$');
+            }
+            out.extend_from_slice(format!("#{}", color).as_bytes());
+
+            let sixels: Vec<u8> = (0..width)
+                .map(|x| {
+                    let bits = (0..6)
+                        .filter(|row| {
+                            let y = band * 6 + row;
+                            y < height && colors[(y * width + x) as usize] == Some(color)
+                        })
+                        .fold(0, |bits, row| bits | 1 << row);
+                    63 + bits
+                })
+                .collect();
+            // Colunas vazias no fim da passada não precisam ser enviadas
+            let end = sixels.iter().rposition(|&sixel| sixel != 63).map_or(0, |last| last + 1);
+            write_sixel_runs(&mut out, &sixels[..end]);
+        }
+        out.push(b'-');
+    }
+
+    out.extend_from_slice(b"\x1b\\");
+    out
+}
+
+// Repetições de um mesmo sixel viram `!<n><sixel>` quando isso encurta a saída
+fn write_sixel_runs(out: &mut Vec<u8>, sixels: &[u8]) {
+    let mut index = 0;
+    while index < sixels.len() {
+        let sixel = sixels[index];
+        let count = sixels[index..].iter().take_while(|&&next| next == sixel).count();
+        if count > 3 {
+            out.extend_from_slice(format!("!{}", count).as_bytes());
+            out.push(sixel);
+        } else {
+            out.extend(std::iter::repeat_n(sixel, count));
+        }
+        index += count;
+    }
+}
+
+// Codificação guardada pelo `Picture`: protocolo, células, tamanho da célula e os bytes
+type Encoding = (GraphicsProtocol, (u16, u16), (u16, u16), Rc<[u8]>);
+
+// Imagem desenhada sobre `cols` x `rows` células a partir da `Position` da entidade
+#[derive(Debug, Clone)]
+pub struct Picture {
+    image: Image,
+    cols: u16,
+    rows: u16,
+    // Última codificação gerada, reaproveitada enquanto o protocolo e o tamanho não mudam
+    cache: RefCell<Option<Encoding>>,
+}
+
+impl Picture {
+    pub fn new(image: Image, cols: u16, rows: u16) -> Self {
+        Self { image, cols, rows, cache: RefCell::new(None) }
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn set_image(&mut self, image: Image) {
+        self.image = image;
+        self.cache.replace(None);
+    }
+
+    // Tamanho em células
+    pub fn size(&self) -> (u16, u16) {
+        (self.cols, self.rows)
+    }
+
+    // Bytes da imagem no protocolo, ocupando `cells` células do terminal de `cell_size` pixels
+    pub(crate) fn encoded(&self, protocol: GraphicsProtocol, cells: (u16, u16), cell_size: (u16, u16)) -> Rc<[u8]> {
+        if let Some((cached_protocol, cached_cells, cached_size, bytes)) = self.cache.borrow().as_ref() {
+            if (*cached_protocol, *cached_cells, *cached_size) == (protocol, cells, cell_size) {
+                return Rc::clone(bytes);
+            }
+        }
+
+        let bytes: Rc<[u8]> = match protocol {
+            GraphicsProtocol::Kitty => encode_kitty(&self.image, cells.0, cells.1).into(),
+            GraphicsProtocol::Sixel => {
+                let width = cells.0 as u32 * cell_size.0 as u32;
+                let height = cells.1 as u32 * cell_size.1 as u32;
+                encode_sixel(&self.image.resized(width, height)).into()
+            }
+            GraphicsProtocol::HalfBlocks => Rc::from(Vec::new()),
+        };
+        self.cache.replace(Some((protocol, cells, cell_size, Rc::clone(&bytes))));
+        bytes
+    }
+}
+
+impl IComponent for Picture {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    #[test]
+    fn base64_pads_partial_groups() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+    }
+
+    #[test]
+    fn kitty_sends_small_image_in_one_chunk() {
+        let image = Image::from_pixels(2, 2, vec![[1, 2, 3, 4]; 4]);
+        let expected = b"\x1b_Ga=T,f=32,s=2,v=2,c=2,r=1,z=-1,C=1,q=2,m=0;AQIDBAECAwQBAgMEAQIDBA==\x1b\\";
+        assert_eq!(encode_kitty(&image, 2, 1), expected);
+    }
+
+    #[test]
+    fn kitty_splits_payload_in_chunks() {
+        // 32x32 RGBA = 4096 bytes = 5464 caracteres de base64: um pedaço cheio e um de 1368
+        let image = Image::from_pixels(32, 32, vec![RED; 32 * 32]);
+        let out = encode_kitty(&image, 4, 2);
+        let payload = base64(&[255, 0, 0, 255].repeat(32 * 32));
+
+        let mut expected = b"\x1b_Ga=T,f=32,s=32,v=32,c=4,r=2,z=-1,C=1,q=2,m=1;".to_vec();
+        expected.extend_from_slice(&payload.as_bytes()[..KITTY_CHUNK]);
+        expected.extend_from_slice(b"\x1b\\\x1b_Gm=0;");
+        expected.extend_from_slice(&payload.as_bytes()[KITTY_CHUNK..]);
+        expected.extend_from_slice(b"\x1b\\");
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn sixel_encodes_colors_and_runs() {
+        let mut pixels = vec![RED; 5];
+        pixels.push(BLUE);
+        let image = Image::from_pixels(6, 1, pixels);
+        let expected = "\x1bP0;1;0q\"1;1;6;1#5;2;0;0;100#180;2;100;0;0#5!5?@$#180!5@-\x1b\\";
+        assert_eq!(String::from_utf8(encode_sixel(&image)).unwrap(), expected);
+    }
+
+    #[test]
+    fn sixel_area_is_not_rewritten_by_cells() {
+        use crate::capabilities::Capabilities;
+        use crate::frame_buffer::FrameBuffer;
+
+        let picture = Picture::new(Image::from_pixels(2, 2, vec![RED; 4]), 2, 1);
+        let mut frame = FrameBuffer::new(8, 1);
+        frame.set_capabilities(Capabilities::full().with_graphics(GraphicsProtocol::Sixel));
+        frame.draw_picture(2, 0, &picture);
+        frame.flush(&mut Vec::new()).unwrap();
+
+        // Uma estrela sobre a imagem não reenvia o sixel; a de fora é escrita normalmente
+        frame.clear();
+        frame.draw_picture(2, 0, &picture);
+        frame.put_char(3, 0, '*');
+        frame.put_char(6, 0, '+');
+        let mut out = Vec::new();
+        frame.flush(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("\x1bP"));
+        assert!(!out.contains('*'));
+        assert!(out.contains('+'));
+    }
+}
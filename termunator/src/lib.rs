@@ -6,9 +6,27 @@ pub mod Components;
 pub mod Entity;
 pub mod Systems;
 pub mod World;
+pub mod frame_buffer;
+pub mod canvas;
+pub mod primitives;
+pub mod figlet;
+pub mod sprite;
+pub mod rexpaint;
+pub mod unicode;
+pub mod capabilities;
+pub mod transform;
+pub mod particles;
+pub mod tilemap;
+pub mod effects;
+pub mod screenshot;
+pub mod recording;
+pub mod graphics;
+pub mod netpbm;
+pub mod ascii_art;
 pub use crossterm::event::KeyCode;
 pub use crossterm::{execute,queue};
 pub use crossterm::terminal::{Clear, ClearType};
+pub use crossterm::style::{Attribute, Attributes, Color, Stylize};
 
 
 
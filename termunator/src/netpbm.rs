@@ -0,0 +1,167 @@
+// netpbm.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Loader for the Netpbm formats: PBM (bitmaps), PGM (grayscale) and PPM (color), both in the
+// plain ASCII variants (P1, P2, P3) and the binary ones (P4, P5, P6). They have no compression,
+// so no external codecs are needed, and any editor (GIMP, ImageMagick...) can export them.
+// Images are returned as an RGBA `Image`, ready for the graphics protocols or the ASCII art
+// converter.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::graphics::Image;
+
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Image, Box<dyn Error>> {
+    let data = fs::read(path)?;
+    parse(&data)
+}
+
+pub fn parse(data: &[u8]) -> Result<Image, Box<dyn Error>> {
+    let mut reader = Reader { data, position: 0 };
+    let magic = reader.token()?;
+    let (binary, channels) = match magic.as_str() {
+        "P1" => (false, 0),
+        "P2" => (false, 1),
+        "P3" => (false, 3),
+        "P4" => (true, 0),
+        "P5" => (true, 1),
+        "P6" => (true, 3),
+        _ => return Err(format!("Não é uma imagem Netpbm: {}", magic).into()),
+    };
+
+    let width = reader.number()?;
+    let height = reader.number()?;
+    // PBM não tem valor máximo: cada pixel é 0 (branco) ou 1 (preto)
+    let max = if channels == 0 { 1 } else { reader.number()? };
+    if max == 0 || max > 65535 {
+        return Err(format!("Valor máximo inválido: {}", max).into());
+    }
+    if binary {
+        // Um único espaço separa o cabeçalho dos dados binários
+        reader.position += 1;
+    }
+
+    // Confere o tamanho contra os dados antes de alocar: cada amostra ocupa ao menos um byte
+    // (no P4, um bit), então um cabeçalho corrompido não consegue pedir gigabytes de memória
+    let count = (width as usize).checked_mul(height as usize);
+    let needed = match (binary, channels) {
+        (true, 0) => (width as usize).div_ceil(8).checked_mul(height as usize),
+        (true, _) => count.and_then(|count| count.checked_mul(channels * if max > 255 { 2 } else { 1 })),
+        (false, _) => count.and_then(|count| count.checked_mul(channels.max(1))),
+    };
+    if needed.is_none_or(|needed| needed > data.len().saturating_sub(reader.position)) {
+        return Err(format!("Imagem de {}x{} maior que o arquivo", width, height).into());
+    }
+    let count = width as usize * height as usize;
+    let samples = match (binary, channels) {
+        (false, 0) => reader.bits(count)?,
+        (false, _) => (0..count * channels).map(|_| reader.number()).collect::<Result<_, _>>()?,
+        (true, 0) => reader.packed_bits(width as usize, height as usize)?,
+        (true, _) => reader.binary(count * channels, max > 255)?,
+    };
+
+    let scale = |sample: u32| (sample.min(max) * 255 / max) as u8;
+    let pixels = match channels {
+        0 => samples.iter().map(|&bit| if bit == 1 { [0, 0, 0, 255] } else { [255, 255, 255, 255] }).collect(),
+        1 => samples.iter().map(|&gray| [scale(gray), scale(gray), scale(gray), 255]).collect(),
+        _ => samples
+            .chunks(3)
+            .map(|rgb| [scale(rgb[0]), scale(rgb[1]), scale(rgb[2]), 255])
+            .collect(),
+    };
+    Ok(Image::from_pixels(width, height, pixels))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    // Próxima palavra do cabeçalho ou dos dados ASCII, pulando espaços e comentários
+    fn token(&mut self) -> Result<String, Box<dyn Error>> {
+        loop {
+            match self.data.get(self.position) {
+                Some(byte) if byte.is_ascii_whitespace() => self.position += 1,
+                Some(b'#') => {
+                    while self.data.get(self.position).is_some_and(|&byte| byte != b'\n') {
+                        self.position += 1;
+                    }
+                }
+                Some(_) => break,
+                None => return Err("Fim inesperado da imagem".into()),
+            }
+        }
+        let start = self.position;
+        while self
+            .data
+            .get(self.position)
+            .is_some_and(|&byte| !byte.is_ascii_whitespace() && byte != b'#')
+        {
+            self.position += 1;
+        }
+        Ok(String::from_utf8_lossy(&self.data[start..self.position]).into_owned())
+    }
+
+    fn number(&mut self) -> Result<u32, Box<dyn Error>> {
+        let token = self.token()?;
+        token
+            .parse()
+            .map_err(|_| format!("Número inválido na imagem: {}", token).into())
+    }
+
+    // No P1 os bits podem vir colados, sem espaços entre eles
+    fn bits(&mut self, count: usize) -> Result<Vec<u32>, Box<dyn Error>> {
+        let mut bits = Vec::with_capacity(count);
+        while bits.len() < count {
+            match self.data.get(self.position) {
+                Some(b'0') => bits.push(0),
+                Some(b'1') => bits.push(1),
+                Some(b'#') => {
+                    self.token()?;
+                    continue;
+                }
+                Some(byte) if byte.is_ascii_whitespace() => {}
+                Some(&byte) => return Err(format!("Bit inválido na imagem: {}", byte as char).into()),
+                None => return Err("Fim inesperado da imagem".into()),
+            }
+            self.position += 1;
+        }
+        Ok(bits)
+    }
+
+    // No P4 cada linha é empacotada em bytes, 8 pixels por byte, do bit mais alto ao mais baixo
+    fn packed_bits(&mut self, width: usize, height: usize) -> Result<Vec<u32>, Box<dyn Error>> {
+        let row_bytes = width.div_ceil(8);
+        let bytes = self.take(row_bytes * height)?;
+        let bits = bytes
+            .chunks(row_bytes.max(1))
+            .flat_map(|row| (0..width).map(move |x| (row[x / 8] >> (7 - x % 8) & 1) as u32))
+            .collect();
+        Ok(bits)
+    }
+
+    // Amostras de 1 byte, ou 2 bytes big-endian quando o valor máximo passa de 255
+    fn binary(&mut self, count: usize, wide: bool) -> Result<Vec<u32>, Box<dyn Error>> {
+        let size = if wide { 2 } else { 1 };
+        let bytes = self.take(count * size)?;
+        let samples = if wide {
+            bytes.chunks(2).map(|pair| (pair[0] as u32) << 8 | pair[1] as u32).collect()
+        } else {
+            bytes.iter().map(|&byte| byte as u32).collect()
+        };
+        Ok(samples)
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        let data = self.data;
+        let bytes = data
+            .get(self.position..self.position + length)
+            .ok_or("Fim inesperado da imagem")?;
+        self.position += length;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,342 @@
+// particles.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Particle effects: an emitter component spawns short-lived glyphs with a velocity, gravity and
+// a lifetime, whose color and glyph change along their life through curves (sparks that go
+// from '*' yellow to '.' dark grey). Particles live in world coordinates, so a moving emitter
+// leaves a trail, and are kept in a fixed pool allocated once per emitter: dead particles are
+// reused instead of allocating every frame. The ParticleSystem simulates them and the
+// DrawSystem renders them with the other drawables.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::any::Any;
+
+use crossterm::style::Color;
+
+use crate::Components::IComponent;
+
+// Limite de passos do `prewarm`; acima disso o passo é aumentado para caber em `seconds`
+const MAX_PREWARM_STEPS: u32 = 10_000;
+
+// Gerador pseudo-aleatório xorshift: rápido, determinístico e sem dependências
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        // Zero é um ponto fixo do xorshift
+        Self { state: seed.max(1) }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    // Número em [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+
+    // Número em [min, max)
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+// Valores que podem ser interpolados ao longo de uma curva
+pub trait Interpolate: Copy {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self;
+}
+
+impl Interpolate for char {
+    // Caracteres não se misturam: troca no próximo ponto da curva
+    fn interpolate(from: Self, _to: Self, _t: f32) -> Self {
+        from
+    }
+}
+
+impl Interpolate for Color {
+    // Cores RGB são misturadas; as da paleta trocam no próximo ponto da curva
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        match (from, to) {
+            (Color::Rgb { r: r0, g: g0, b: b0 }, Color::Rgb { r: r1, g: g1, b: b1 }) => {
+                let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+                Color::Rgb { r: mix(r0, r1), g: mix(g0, g1), b: mix(b0, b1) }
+            }
+            _ => from,
+        }
+    }
+}
+
+// Valor em função da vida da partícula: pontos (fração da vida de 0 a 1, valor)
+#[derive(Debug, Clone)]
+pub struct Curve<T> {
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: Interpolate> Curve<T> {
+    pub fn new(mut keys: Vec<(f32, T)>) -> Self {
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { keys }
+    }
+
+    pub fn constant(value: T) -> Self {
+        Self { keys: vec![(0.0, value)] }
+    }
+
+    // Pontos igualmente espaçados ao longo da vida
+    pub fn even(values: &[T]) -> Self {
+        let step = 1.0 / values.len().max(1) as f32;
+        Self::new(values.iter().enumerate().map(|(i, value)| (i as f32 * step, *value)).collect())
+    }
+
+    pub fn sample(&self, t: f32) -> Option<T> {
+        let next = self.keys.iter().position(|(key, _)| *key > t);
+        match next {
+            Some(0) => self.keys.first().map(|(_, value)| *value),
+            Some(next) => {
+                let (from_t, from) = self.keys[next - 1];
+                let (to_t, to) = self.keys[next];
+                Some(T::interpolate(from, to, (t - from_t) / (to_t - from_t)))
+            }
+            None => self.keys.last().map(|(_, value)| *value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub age: f32,
+    pub lifetime: f32,
+    pub alive: bool,
+}
+
+impl Particle {
+    // Fração da vida já vivida, de 0 a 1
+    pub fn life(&self) -> f32 {
+        (self.age / self.lifetime.max(f32::EPSILON)).min(1.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    // Partículas por segundo enquanto `emitting` for verdadeiro
+    pub rate: f32,
+    pub emitting: bool,
+    // Deslocamento do ponto de emissão em relação à `Position` da entidade
+    pub offset: (f32, f32),
+    // Área retangular (largura, altura) a partir do ponto de emissão onde as partículas nascem
+    pub area: (f32, f32),
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    // Direção em graus (0 é para a direita, 90 para baixo) e abertura total do cone
+    pub direction: f32,
+    pub spread: f32,
+    // Aceleração vertical em células por segundo ao quadrado
+    pub gravity: f32,
+    pub colors: Curve<Color>,
+    pub glyphs: Curve<char>,
+    particles: Vec<Particle>,
+    // Índices das partículas mortas, prontas para reuso
+    free: Vec<usize>,
+    pending: usize,
+    accumulator: f32,
+    rng: Rng,
+    started: bool,
+    finished: bool,
+}
+
+impl ParticleEmitter {
+    // Cria um emissor com espaço para até `capacity` partículas vivas
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            rate: 0.0,
+            emitting: true,
+            offset: (0.0, 0.0),
+            area: (0.0, 0.0),
+            lifetime: (1.0, 1.0),
+            speed: (1.0, 1.0),
+            direction: 0.0,
+            spread: 360.0,
+            gravity: 0.0,
+            colors: Curve::constant(Color::Reset),
+            glyphs: Curve::constant('*'),
+            particles: vec![Particle::default(); capacity],
+            free: (0..capacity).rev().collect(),
+            pending: 0,
+            accumulator: 0.0,
+            rng: Rng::new(0x2545_f491),
+            started: false,
+            finished: false,
+        }
+    }
+
+    pub fn with_rate(mut self, rate: f32) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    pub fn with_area(mut self, width: f32, height: f32) -> Self {
+        self.area = (width, height);
+        self
+    }
+
+    pub fn with_lifetime(mut self, min: f32, max: f32) -> Self {
+        self.lifetime = (min, max);
+        self
+    }
+
+    pub fn with_speed(mut self, min: f32, max: f32) -> Self {
+        self.speed = (min, max);
+        self
+    }
+
+    pub fn with_direction(mut self, degrees: f32, spread: f32) -> Self {
+        self.direction = degrees;
+        self.spread = spread;
+        self
+    }
+
+    pub fn with_gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_colors(mut self, colors: Curve<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn with_glyphs(mut self, glyphs: Curve<char>) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    // Dispara `count` partículas de uma vez na próxima atualização
+    pub fn burst(mut self, count: usize) -> Self {
+        self.pending += count;
+        self
+    }
+
+    pub fn emit(&mut self, count: usize) {
+        self.pending += count;
+    }
+
+    pub fn alive(&self) -> usize {
+        self.particles.len() - self.free.len()
+    }
+
+    pub fn particles(&self) -> impl Iterator<Item = &Particle> {
+        self.particles.iter().filter(|particle| particle.alive)
+    }
+
+    // Um emissor sem taxa contínua termina quando suas rajadas acabam
+    pub fn is_finished(&self) -> bool {
+        self.started && self.pending == 0 && self.alive() == 0 && (!self.emitting || self.rate <= 0.0)
+    }
+
+    // Avança a simulação; `origin` é a posição da entidade. Retorna `true` no passo em que o emissor termina
+    pub fn update(&mut self, origin: (f32, f32), delta_time: f32) -> bool {
+        for (index, particle) in self.particles.iter_mut().enumerate() {
+            if !particle.alive {
+                continue;
+            }
+            particle.age += delta_time;
+            if particle.age >= particle.lifetime {
+                particle.alive = false;
+                self.free.push(index);
+                continue;
+            }
+            particle.vy += self.gravity * delta_time;
+            particle.x += particle.vx * delta_time;
+            particle.y += particle.vy * delta_time;
+        }
+
+        if self.emitting && self.rate > 0.0 {
+            self.accumulator += self.rate * delta_time;
+            let count = self.accumulator as usize;
+            self.accumulator -= count as f32;
+            self.pending += count;
+        }
+        let spawn = std::mem::take(&mut self.pending);
+        for _ in 0..spawn {
+            self.spawn(origin);
+        }
+        self.started |= spawn > 0;
+
+        let finished = self.is_finished() && !self.finished;
+        self.finished |= finished;
+        finished
+    }
+
+    // Simula alguns segundos de uma vez, para começar com o efeito já em andamento (ex: estrelas)
+    pub fn prewarm(&mut self, origin: (f32, f32), seconds: f32, step: f32) {
+        // Um passo zero, negativo ou NaN nunca alcançaria `seconds`
+        if step.is_nan() || step <= 0.0 || !seconds.is_finite() {
+            return;
+        }
+        let step = step.max(seconds / MAX_PREWARM_STEPS as f32);
+        // Conta os passos antes, já que somar `step` em um f32 pode parar de avançar
+        let steps = ((seconds / step).ceil() as u32).min(MAX_PREWARM_STEPS);
+        for _ in 0..steps {
+            self.update(origin, step);
+        }
+    }
+
+    fn spawn(&mut self, origin: (f32, f32)) {
+        // Sem espaço livre, a partícula é descartada
+        let Some(index) = self.free.pop() else {
+            return;
+        };
+
+        let rng = &mut self.rng;
+        let angle = (self.direction + rng.range(-0.5, 0.5) * self.spread).to_radians();
+        let speed = rng.range(self.speed.0, self.speed.1);
+        self.particles[index] = Particle {
+            x: origin.0 + self.offset.0 + rng.range(0.0, self.area.0),
+            y: origin.1 + self.offset.1 + rng.range(0.0, self.area.1),
+            vx: angle.cos() * speed,
+            vy: angle.sin() * speed,
+            age: 0.0,
+            lifetime: rng.range(self.lifetime.0, self.lifetime.1),
+            alive: true,
+        };
+    }
+
+    // Caractere e cor da partícula no momento atual da sua vida
+    pub fn appearance(&self, particle: &Particle) -> (char, Color) {
+        let life = particle.life();
+        let glyph = self.glyphs.sample(life).unwrap_or('*');
+        let color = self.colors.sample(life).unwrap_or(Color::Reset);
+        (glyph, color)
+    }
+}
+
+impl IComponent for ParticleEmitter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,209 @@
+// primitives.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Drawing primitives for the FrameBuffer: lines, rectangles, circles, ellipses, flood fill
+// and box-drawing borders. Every primitive writes cells through `FrameBuffer::set`, so
+// clipping and the current view (see viewports) apply to them like to any Body.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use crossterm::style::Color;
+
+use crate::frame_buffer::{Cell, FrameBuffer, Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+    Single,
+    Double,
+    Rounded,
+    Heavy,
+    // '+', '-' e '|' para terminais sem suporte a box-drawing
+    Ascii,
+    // Mesmo caractere em toda a moldura
+    Char(char),
+}
+
+// Caracteres de uma moldura: cantos e linhas
+pub struct BorderSet {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BorderStyle {
+    pub fn set(&self) -> BorderSet {
+        let chars = match self {
+            BorderStyle::Single => ['┌', '┐', '└', '┘', '─', '│'],
+            BorderStyle::Double => ['╔', '╗', '╚', '╝', '═', '║'],
+            BorderStyle::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            BorderStyle::Heavy => ['┏', '┓', '┗', '┛', '━', '┃'],
+            BorderStyle::Ascii => ['+', '+', '+', '+', '-', '|'],
+            BorderStyle::Char(ch) => [*ch; 6],
+        };
+        BorderSet {
+            top_left: chars[0],
+            top_right: chars[1],
+            bottom_left: chars[2],
+            bottom_right: chars[3],
+            horizontal: chars[4],
+            vertical: chars[5],
+        }
+    }
+}
+
+impl FrameBuffer {
+    // Linha entre dois pontos (algoritmo de Bresenham)
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, cell: Cell) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (step_x, step_y) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y) = (x0, y0);
+        let mut error = dx + dy;
+
+        loop {
+            self.set(x, y, cell);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    // Contorno de um retângulo
+    pub fn rect(&mut self, rect: Rect, cell: Cell) {
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let (right, bottom) = (rect.right() - 1, rect.bottom() - 1);
+        self.line(rect.x, rect.y, right, rect.y, cell);
+        self.line(rect.x, bottom, right, bottom, cell);
+        self.line(rect.x, rect.y, rect.x, bottom, cell);
+        self.line(right, rect.y, right, bottom, cell);
+    }
+
+    pub fn fill_rect(&mut self, rect: Rect, cell: Cell) {
+        for y in rect.y..rect.bottom() {
+            for x in rect.x..rect.right() {
+                self.set(x, y, cell);
+            }
+        }
+    }
+
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: i32, cell: Cell) {
+        self.ellipse(cx, cy, radius, radius, cell);
+    }
+
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, cell: Cell) {
+        self.fill_ellipse(cx, cy, radius, radius, cell);
+    }
+
+    // Contorno de uma elipse com raios `rx` e `ry` (algoritmo do ponto médio)
+    pub fn ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, cell: Cell) {
+        for (x, y) in ellipse_points(rx, ry) {
+            self.set(cx + x, cy + y, cell);
+            self.set(cx - x, cy + y, cell);
+            self.set(cx + x, cy - y, cell);
+            self.set(cx - x, cy - y, cell);
+        }
+    }
+
+    pub fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, cell: Cell) {
+        for (x, y) in ellipse_points(rx, ry) {
+            self.line(cx - x, cy + y, cx + x, cy + y, cell);
+            self.line(cx - x, cy - y, cx + x, cy - y, cell);
+        }
+    }
+
+    // Preenche a região contígua (4 vizinhos) de células iguais à de (x, y)
+    pub fn flood_fill(&mut self, x: i32, y: i32, cell: Cell) {
+        let Some(target) = self.get(x, y).copied() else {
+            return;
+        };
+        if target == cell {
+            return;
+        }
+
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            if self.get(x, y) != Some(&target) {
+                continue;
+            }
+            self.set(x, y, cell);
+            stack.extend([(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]);
+        }
+    }
+
+    // Moldura ao redor do retângulo com caracteres de box-drawing
+    pub fn border(&mut self, rect: Rect, style: BorderStyle, color: Color) {
+        if rect.width < 2 || rect.height < 2 {
+            return;
+        }
+        let set = style.set();
+        let cell = |ch: char| Cell::new(ch).with_fg(color);
+        let (right, bottom) = (rect.right() - 1, rect.bottom() - 1);
+
+        for x in rect.x + 1..right {
+            self.set(x, rect.y, cell(set.horizontal));
+            self.set(x, bottom, cell(set.horizontal));
+        }
+        for y in rect.y + 1..bottom {
+            self.set(rect.x, y, cell(set.vertical));
+            self.set(right, y, cell(set.vertical));
+        }
+        self.set(rect.x, rect.y, cell(set.top_left));
+        self.set(right, rect.y, cell(set.top_right));
+        self.set(rect.x, bottom, cell(set.bottom_left));
+        self.set(right, bottom, cell(set.bottom_right));
+    }
+}
+
+// Pontos de um quadrante da elipse centrada na origem; os outros são obtidos por simetria
+fn ellipse_points(rx: i32, ry: i32) -> Vec<(i32, i32)> {
+    let (rx, ry) = (rx.max(0) as i64, ry.max(0) as i64);
+    let mut points = Vec::new();
+    if rx == 0 || ry == 0 {
+        // Elipse degenerada: uma linha
+        points.extend((0..=rx).map(|x| (x as i32, 0)));
+        points.extend((0..=ry).map(|y| (0, y as i32)));
+        return points;
+    }
+
+    let (rx2, ry2) = (rx * rx, ry * ry);
+    let (mut x, mut y) = (0, ry);
+
+    // Região 1: inclinação menor que 1
+    let mut d1 = 4 * ry2 - 4 * rx2 * ry + rx2;
+    while ry2 * x <= rx2 * y {
+        points.push((x as i32, y as i32));
+        if d1 < 0 {
+            d1 += 4 * ry2 * (2 * x + 3);
+        } else {
+            d1 += 4 * ry2 * (2 * x + 3) - 8 * rx2 * (y - 1);
+            y -= 1;
+        }
+        x += 1;
+    }
+
+    // Região 2: inclinação maior que 1
+    let mut d2 = ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+    while y >= 0 {
+        points.push((x as i32, y as i32));
+        if d2 > 0 {
+            d2 += 4 * rx2 * (3 - 2 * y);
+        } else {
+            d2 += 4 * ry2 * (2 * x + 2) + 4 * rx2 * (3 - 2 * y);
+            x += 1;
+        }
+        y -= 1;
+    }
+    points
+}
@@ -0,0 +1,133 @@
+// recording.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Gameplay recording in the asciicast v2 format used by asciinema. While a Recorder is attached
+// to the FrameBuffer, every flush appends the exact bytes written to the terminal as an output
+// event with its timestamp, so a session can be replayed with `asciinema play` or embedded in a
+// web page with the asciinema player. Terminal resizes become resize events. Recording can be
+// toggled at runtime (see the RecordingSystem) and TERMUNATOR_RECORD starts it at launch: a
+// path ending in .cast is used as is, anything else is a directory for timestamped files.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub struct Recorder {
+    path: PathBuf,
+    out: BufWriter<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    // Cria o arquivo e escreve o cabeçalho com o tamanho do terminal
+    pub fn create<P: AsRef<Path>>(path: P, width: u16, height: u16) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut out = BufWriter::new(File::create(&path)?);
+
+        let term = env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_owned());
+        writeln!(
+            out,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"env\": {{\"TERM\": {}}}}}",
+            width,
+            height,
+            unix_time(),
+            json_string(&term)
+        )?;
+
+        Ok(Self { path, out, start: Instant::now() })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Registra os bytes enviados ao terminal neste instante
+    pub fn output(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let data = json_string(&String::from_utf8_lossy(bytes));
+        self.event("o", &data)
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        let size = json_string(&format!("{}x{}", width, height));
+        self.event("r", &size)
+    }
+
+    fn event(&mut self, kind: &str, data: &str) -> io::Result<()> {
+        let time = self.start.elapsed().as_secs_f64();
+        writeln!(self.out, "[{:.6}, \"{}\", {}]", time, kind, data)
+    }
+
+    // Grava o que ainda está no buffer e fecha o arquivo
+    pub fn finish(mut self) -> io::Result<PathBuf> {
+        self.out.flush()?;
+        Ok(self.path)
+    }
+}
+
+// Caminho de gravação pedido por TERMUNATOR_RECORD, se houver
+pub fn path_from_env() -> Option<PathBuf> {
+    let value = env::var_os("TERMUNATOR_RECORD").filter(|value| !value.is_empty())?;
+    let path = PathBuf::from(value);
+    if path.extension().is_some_and(|extension| extension == "cast") {
+        Some(path)
+    } else {
+        Some(next_path(&path))
+    }
+}
+
+// <diretório>/<nome do executável>-<segundos desde 1970>-<contador>.cast; o contador evita
+// sobrescrever uma gravação quando duas começam no mesmo segundo
+pub fn next_path(directory: &Path) -> PathBuf {
+    static COUNT: AtomicU32 = AtomicU32::new(0);
+    let name = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "termunator".to_owned());
+    let seconds = unix_time();
+    loop {
+        let count = COUNT.fetch_add(1, Ordering::Relaxed) + 1;
+        let path = directory.join(format!("{}-{}-{}.cast", name, seconds, count));
+        // Arquivos de outra execução do jogo no mesmo segundo também são preservados
+        if !path.exists() {
+            return path;
+        }
+    }
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+// Texto como string JSON, com aspas e escapes (a saída do terminal é cheia de ESC)
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            ch if (ch as u32) < 0x20 || ch == '\u{7f}' => {
+                let _ = write!(json, "\\u{:04x}", ch as u32);
+            }
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+    json
+}
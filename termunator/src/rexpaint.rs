@@ -0,0 +1,167 @@
+// rexpaint.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Import of REXPaint images (.xp). An .xp file is a gzip-compressed grid of cells split in
+// layers; each cell stores a CP437 glyph code plus RGB foreground and background colors, and
+// cells whose background is magenta (255, 0, 255) are transparent. Every layer becomes a
+// colored Body, and `flatten` composes them into one. Glyphs are mapped from CP437 to Unicode.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::error::Error;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use crossterm::style::Color;
+use flate2::read::GzDecoder;
+
+use crate::Components::Body;
+use crate::frame_buffer::Cell;
+
+// Fundo que o REXPaint usa para marcar células transparentes
+const TRANSPARENT_BG: (u8, u8, u8) = (255, 0, 255);
+
+// CP437: códigos 0..32 (símbolos de controle) e 127..256; o resto é ASCII
+const CP437_LOW: [char; 32] = [
+    ' ', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼',
+    '►', '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+const CP437_HIGH: [char; 129] = [
+    '⌂',
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', ' ',
+];
+
+// Converte um código CP437 no caractere Unicode equivalente
+pub fn cp437_to_char(code: u32) -> char {
+    match code {
+        0..=31 => CP437_LOW[code as usize],
+        32..=126 => char::from_u32(code).unwrap_or(' '),
+        127..=255 => CP437_HIGH[code as usize - 127],
+        // Fontes estendidas do REXPaint podem usar códigos maiores; trata como Unicode
+        _ => char::from_u32(code).unwrap_or(' '),
+    }
+}
+
+pub struct XpImage {
+    pub version: i32,
+    // Camadas na ordem do arquivo: a primeira fica embaixo
+    pub layers: Vec<Body>,
+}
+
+impl XpImage {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let data = fs::read(path)?;
+        Self::parse(&data)
+    }
+
+    // Lê um arquivo .xp comprimido
+    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut raw = Vec::new();
+        GzDecoder::new(data).read_to_end(&mut raw)?;
+        Self::parse_raw(&raw)
+    }
+
+    // Lê o conteúdo já descomprimido de um .xp
+    pub fn parse_raw(raw: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut reader = Reader { data: raw, offset: 0 };
+
+        // Versões novas começam com um número negativo; as antigas vão direto ao número de camadas
+        let first = reader.i32()?;
+        let (version, layer_count) = if first < 0 { (first, reader.i32()?) } else { (0, first) };
+        if !(0..=64).contains(&layer_count) {
+            return Err(format!("Número de camadas inválido: {}", layer_count).into());
+        }
+
+        let mut layers = Vec::with_capacity(layer_count as usize);
+        for _ in 0..layer_count {
+            let width = reader.i32()?;
+            let height = reader.i32()?;
+            if width < 0 || height < 0 {
+                return Err(format!("Tamanho de camada inválido: {}x{}", width, height).into());
+            }
+            let (width, height) = (width as usize, height as usize);
+            // Cada célula ocupa 10 bytes; confere antes de alocar, para um cabeçalho corrompido
+            // não pedir gigabytes de memória
+            let remaining = raw.len() - reader.offset;
+            if width.checked_mul(height).and_then(|cells| cells.checked_mul(10)).is_none_or(|size| size > remaining) {
+                return Err(format!("Camada de {}x{} maior que o arquivo", width, height).into());
+            }
+
+            // As células ficam em ordem de colunas: de cima para baixo, da esquerda para a direita
+            let mut mat = vec![vec![Cell::BLANK; width]; height];
+            for col in 0..width {
+                for row in mat.iter_mut() {
+                    let glyph = reader.u32()?;
+                    let fg = reader.rgb()?;
+                    let bg = reader.rgb()?;
+                    if bg != TRANSPARENT_BG {
+                        row[col] = Cell::new(cp437_to_char(glyph)).with_fg(rgb(fg)).with_bg(rgb(bg));
+                    }
+                }
+            }
+            // Só as células transparentes ficam com fundo `Reset`
+            layers.push(Body::from_cells(mat).with_transparent(' '));
+        }
+
+        Ok(Self { version, layers })
+    }
+
+    // Junta todas as camadas em um só `Body`; camadas de cima cobrem as de baixo
+    pub fn flatten(&self) -> Body {
+        let width = self.layers.iter().map(|layer| layer.size().0).max().unwrap_or(0) as usize;
+        let height = self.layers.iter().map(|layer| layer.size().1).max().unwrap_or(0) as usize;
+
+        let mut mat = vec![vec![Cell::BLANK; width]; height];
+        for layer in &self.layers {
+            for (row, cells) in layer.mat.iter().enumerate() {
+                for (col, cell) in cells.iter().enumerate() {
+                    if !layer.is_transparent(cell) {
+                        mat[row][col] = *cell;
+                    }
+                }
+            }
+        }
+        Body::from_cells(mat).with_transparent(' ')
+    }
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+// Leitura sequencial dos campos little-endian do arquivo
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], Box<dyn Error>> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + N)
+            .ok_or("Arquivo .xp truncado")?;
+        self.offset += N;
+        Ok(bytes.try_into()?)
+    }
+
+    fn i32(&mut self) -> Result<i32, Box<dyn Error>> {
+        Ok(i32::from_le_bytes(self.bytes()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.bytes()?))
+    }
+
+    fn rgb(&mut self) -> Result<(u8, u8, u8), Box<dyn Error>> {
+        let [r, g, b] = self.bytes()?;
+        Ok((r, g, b))
+    }
+}
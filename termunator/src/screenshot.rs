@@ -0,0 +1,245 @@
+// screenshot.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Screenshots of the frame buffer, for bug reports, documentation of the games and golden-image
+// tests of what the DrawSystem renders. A frame is exported as plain text (glyphs only), as
+// ANSI-escaped text that shows the colors again with `cat`, or as a standalone HTML page.
+// Games call `save` (or `World::save_screenshot`) directly, or add the ScreenshotSystem, which
+// writes a file when its hotkey is pressed or a `GameEvent::Screenshot` is sent.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crossterm::queue;
+use crossterm::style::{Attribute, Attributes, Color, ResetColor, SetAttribute};
+
+use crate::capabilities::to_rgb;
+use crate::frame_buffer::{write_style, Cell, FrameBuffer};
+
+// Cores usadas no HTML para `Color::Reset`
+const DEFAULT_FG: (u8, u8, u8) = (229, 229, 229);
+const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    // Só os caracteres, sem cores; espaços no fim das linhas são removidos
+    Text,
+    // Texto com sequências de escape ANSI para cores e atributos
+    Ansi,
+    // Página HTML com um <pre> estilizado
+    Html,
+}
+
+impl ScreenshotFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ScreenshotFormat::Text => "txt",
+            ScreenshotFormat::Ansi => "ans",
+            ScreenshotFormat::Html => "html",
+        }
+    }
+
+    // Formato correspondente à extensão do arquivo
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "txt" => Some(ScreenshotFormat::Text),
+            "ans" | "ansi" => Some(ScreenshotFormat::Ansi),
+            "html" | "htm" => Some(ScreenshotFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+// Salva o frame atual; o formato vem da extensão do arquivo
+pub fn save<P: AsRef<Path>>(frame: &FrameBuffer, path: P) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    let format = ScreenshotFormat::from_path(path)
+        .ok_or_else(|| format!("Formato de screenshot desconhecido: {}", path.display()))?;
+    save_as(frame, path, format)
+}
+
+pub fn save_as<P: AsRef<Path>>(frame: &FrameBuffer, path: P, format: ScreenshotFormat) -> Result<(), Box<dyn Error>> {
+    fs::write(path, render(frame, format))?;
+    Ok(())
+}
+
+pub fn render(frame: &FrameBuffer, format: ScreenshotFormat) -> String {
+    match format {
+        ScreenshotFormat::Text => to_text(frame),
+        ScreenshotFormat::Ansi => to_ansi(frame),
+        ScreenshotFormat::Html => to_html(frame),
+    }
+}
+
+// Linhas do back buffer, que contém o frame desenhado (e, depois do flush, o que está na tela)
+fn rows(frame: &FrameBuffer) -> impl Iterator<Item = &[Cell]> {
+    let (width, _) = frame.size();
+    frame.cells().chunks(width.max(1) as usize)
+}
+
+pub fn to_text(frame: &FrameBuffer) -> String {
+    let mut text = String::new();
+    for row in rows(frame) {
+        let mut line = String::new();
+        for cell in row {
+            let _ = write!(line, "{}", cell.symbol());
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+pub fn to_ansi(frame: &FrameBuffer) -> String {
+    let mut bytes = Vec::new();
+    write_ansi(frame, &mut bytes).expect("Erro ao escrever screenshot em memória");
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// Cada linha começa e termina sem estilo, para o arquivo poder ser cortado ou concatenado
+pub fn write_ansi<W: Write>(frame: &FrameBuffer, out: &mut W) -> io::Result<()> {
+    for row in rows(frame) {
+        let mut current = (Color::Reset, Color::Reset, Attributes::none());
+        for cell in row.iter().filter(|cell| !cell.is_continuation()) {
+            write_style(out, &mut current, cell)?;
+            write!(out, "{}", cell.symbol())?;
+        }
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+pub fn to_html(frame: &FrameBuffer) -> String {
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html>\n<head>\n<meta charset=\"utf-8\">\n<title>termunator</title>");
+    let _ = writeln!(
+        html,
+        "<style>\nbody {{ background: {bg}; }}\npre {{ font-family: monospace; line-height: 1.2; color: {fg}; background: {bg}; }}\n</style>",
+        fg = hex(DEFAULT_FG),
+        bg = hex(DEFAULT_BG)
+    );
+    html.push_str("</head>\n<body>\n<pre>\n");
+
+    for row in rows(frame) {
+        // Células vizinhas com o mesmo estilo ficam no mesmo <span>
+        let mut cells = row.iter().filter(|cell| !cell.is_continuation()).peekable();
+        while let Some(first) = cells.next() {
+            let mut text = escape(&first.symbol().to_string());
+            while let Some(cell) = cells.next_if(|cell| same_style(cell, first)) {
+                text.push_str(&escape(&cell.symbol().to_string()));
+            }
+            match css(first) {
+                style if style.is_empty() => html.push_str(&text),
+                style => {
+                    let _ = write!(html, "<span style=\"{}\">{}</span>", style, text);
+                }
+            }
+        }
+        html.push('\n');
+    }
+
+    html.push_str("</pre>\n</body>\n</html>\n");
+    html
+}
+
+fn same_style(a: &Cell, b: &Cell) -> bool {
+    a.fg == b.fg && a.bg == b.bg && a.attrs == b.attrs
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+// Estilo CSS da célula; vazio quando ela usa as cores e atributos padrão
+fn css(cell: &Cell) -> String {
+    let (mut fg, mut bg) = (to_rgb(cell.fg), to_rgb(cell.bg));
+    if cell.attrs.has(Attribute::Reverse) {
+        (fg, bg) = (Some(bg.unwrap_or(DEFAULT_BG)), Some(fg.unwrap_or(DEFAULT_FG)));
+    }
+
+    let mut style = Vec::new();
+    if let Some(fg) = fg {
+        style.push(format!("color: {}", hex(fg)));
+    }
+    if let Some(bg) = bg {
+        style.push(format!("background: {}", hex(bg)));
+    }
+    let attributes = [
+        (Attribute::Bold, "font-weight: bold"),
+        (Attribute::Dim, "opacity: 0.6"),
+        (Attribute::Italic, "font-style: italic"),
+        (Attribute::Underlined, "text-decoration: underline"),
+        (Attribute::CrossedOut, "text-decoration: line-through"),
+        (Attribute::Hidden, "visibility: hidden"),
+    ];
+    for (attribute, rule) in attributes {
+        if cell.attrs.has(attribute) {
+            style.push(rule.to_owned());
+        }
+    }
+    style.join("; ")
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "a<b" sem estilo seguido de "xy" vermelho e negrito, e "日本" na segunda linha
+    fn sample() -> FrameBuffer {
+        let mut frame = FrameBuffer::new(6, 2);
+        frame.put_str(0, 0, "a<b");
+        for (x, ch) in [(3, 'x'), (4, 'y')] {
+            frame.set(x, 0, Cell::new(ch).with_fg(Color::Red).with_attr(Attribute::Bold));
+        }
+        frame.put_str(0, 1, "日本");
+        frame
+    }
+
+    #[test]
+    fn text_trims_trailing_spaces_and_skips_continuations() {
+        assert_eq!(to_text(&sample()), "a<bxy\n日本\n");
+    }
+
+    #[test]
+    fn ansi_skips_continuations() {
+        let mut frame = FrameBuffer::new(3, 1);
+        frame.put_str(0, 0, "日x");
+        assert_eq!(to_ansi(&frame), "日x\x1b[0m\x1b[0m\n");
+    }
+
+    #[test]
+    fn html_escapes_and_groups_styled_runs() {
+        let html = to_html(&sample());
+        assert!(html.contains("\na&lt;b<span style=\"color: #ff0000; font-weight: bold\">xy</span> \n日本  \n"));
+    }
+
+    #[test]
+    fn html_reverse_swaps_colors() {
+        let plain = Cell::new('r').with_attr(Attribute::Reverse);
+        assert_eq!(css(&plain), "color: #000000; background: #e5e5e5");
+
+        let red = plain.with_fg(Color::Red);
+        assert_eq!(css(&red), "color: #000000; background: #ff0000");
+    }
+}
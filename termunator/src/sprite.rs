@@ -0,0 +1,353 @@
+// sprite.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Plain-text sprite files, so art can be edited outside the Rust code. A sprite file holds
+// one or more frames of ASCII art grouped in named clips, with an optional color layer per
+// frame and an anchor point; it loads into a Body (first frame) or an Animation (all clips).
+// Lines starting with '@' are directives, everything else is art:
+//
+//   @# comment                 @anchor <col> <row>       @fg <color name>
+//   @transparent <char>        @opaque                   @clip <name> [loop|pingpong|once]
+//   @frame [seconds]           @colors                   @@ art line that starts with '@'
+//
+// The color layer uses one letter per cell (see `color_code`); ' ' and '.' keep the default.
+// The `body!` macro turns string literals into a Body for small sprites kept in code: the rows
+// are decoded and checked at compile time, so only the Body's vectors are allocated at runtime.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crossterm::style::Color;
+
+use crate::Components::{Animation, Body, Clip, Frame, PlayMode};
+use crate::frame_buffer::Cell;
+
+// Duração de um quadro quando `@frame` não informa
+const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
+// Cria um `Body` a partir de linhas literais: `body![" ^^ ", "/00\\"]`. As linhas são
+// decodificadas em tempo de compilação, um caractere por célula (duas nos largos), e um
+// caractere de controle (tabulação, quebra de linha...) é erro de compilação
+#[macro_export]
+macro_rules! body {
+    ($($line:literal),* $(,)?) => {{
+        const LINES: &[&str] = &[$($line),*];
+        const ROWS: [[char; $crate::sprite::max_chars(LINES)]; LINES.len()] = $crate::sprite::decode_lines(LINES);
+        const LENGTHS: [usize; LINES.len()] = $crate::sprite::line_lengths(LINES);
+        $crate::Components::Body::new(ROWS.iter().zip(LENGTHS).map(|(row, length)| row[..length].to_vec()).collect())
+    }};
+}
+
+// Funções usadas pelo `body!` em tempo de compilação
+
+// Quantidade de caracteres de uma linha: conta os bytes que não continuam um caractere UTF-8
+#[doc(hidden)]
+pub const fn char_count(line: &str) -> usize {
+    let bytes = line.as_bytes();
+    let (mut count, mut index) = (0, 0);
+    while index < bytes.len() {
+        if bytes[index] & 0xC0 != 0x80 {
+            count += 1;
+        }
+        index += 1;
+    }
+    count
+}
+
+#[doc(hidden)]
+pub const fn max_chars(lines: &[&str]) -> usize {
+    let (mut max, mut index) = (0, 0);
+    while index < lines.len() {
+        let count = char_count(lines[index]);
+        if count > max {
+            max = count;
+        }
+        index += 1;
+    }
+    max
+}
+
+#[doc(hidden)]
+pub const fn line_lengths<const H: usize>(lines: &[&str]) -> [usize; H] {
+    let mut lengths = [0; H];
+    let mut index = 0;
+    while index < H {
+        lengths[index] = char_count(lines[index]);
+        index += 1;
+    }
+    lengths
+}
+
+// Caracteres de cada linha, completados com espaços até a mais longa
+#[doc(hidden)]
+pub const fn decode_lines<const W: usize, const H: usize>(lines: &[&str]) -> [[char; W]; H] {
+    let mut rows = [[' '; W]; H];
+    let mut y = 0;
+    while y < H {
+        let bytes = lines[y].as_bytes();
+        let (mut index, mut x) = (0, 0);
+        while index < bytes.len() {
+            // O primeiro byte diz quantos bytes o caractere ocupa
+            let (length, mut code) = match bytes[index] {
+                byte if byte < 0x80 => (1, byte as u32),
+                byte if byte >= 0xF0 => (4, (byte & 0x07) as u32),
+                byte if byte >= 0xE0 => (3, (byte & 0x0F) as u32),
+                byte => (2, (byte & 0x1F) as u32),
+            };
+            let mut next = 1;
+            while next < length {
+                code = code << 6 | (bytes[index + next] & 0x3F) as u32;
+                next += 1;
+            }
+            if code < 0x20 || (code >= 0x7F && code <= 0x9F) {
+                panic!("body!: caractere de controle em uma linha");
+            }
+            rows[y][x] = match char::from_u32(code) {
+                Some(ch) => ch,
+                None => panic!("body!: caractere inválido"),
+            };
+            index += length;
+            x += 1;
+        }
+        y += 1;
+    }
+    rows
+}
+
+#[derive(Debug, Clone)]
+pub struct Sprite {
+    // Clipes na ordem do arquivo; o primeiro é o padrão
+    pub clips: Vec<(String, Clip)>,
+}
+
+// Quadro ainda em texto, antes de aplicar as opções do arquivo
+struct RawFrame {
+    clip: usize,
+    duration: f32,
+    art: Vec<String>,
+    colors: Option<Vec<String>>,
+}
+
+impl Sprite {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut clips: Vec<(String, PlayMode)> = Vec::new();
+        let mut frames: Vec<RawFrame> = Vec::new();
+        let mut anchor = (0, 0);
+        let mut fg = Color::Reset;
+        let mut transparent = Some(' ');
+
+        for (number, line) in content.lines().enumerate() {
+            let number = number + 1;
+            let line = line.trim_end_matches('\r');
+
+            let Some(directive) = line.strip_prefix('@').filter(|rest| !rest.starts_with('@')) else {
+                // Linha de arte; `@@` escapa um '@' no começo
+                let art = line.strip_prefix('@').unwrap_or(line);
+                let needs_frame = frames.last().map(|frame| frame.clip + 1) != Some(clips.len());
+                if needs_frame {
+                    // Linhas vazias antes da arte são ignoradas
+                    if art.trim().is_empty() {
+                        continue;
+                    }
+                    frames.push(new_frame(&mut clips, DEFAULT_FRAME_DURATION));
+                }
+                let frame = frames.last_mut().unwrap();
+                match &mut frame.colors {
+                    Some(colors) => colors.push(art.to_owned()),
+                    None => frame.art.push(art.to_owned()),
+                }
+                continue;
+            };
+
+            let mut args = directive.split_whitespace();
+            let name = args.next().unwrap_or("");
+            match name {
+                _ if name.starts_with('#') => {}
+                "anchor" => {
+                    let mut coord = || -> Result<i32, Box<dyn Error>> {
+                        let value = args.next().ok_or(format!("Linha {}: @anchor precisa de coluna e linha", number))?;
+                        Ok(value.parse()?)
+                    };
+                    anchor = (coord()?, coord()?);
+                }
+                "fg" => {
+                    let value = args.next().ok_or(format!("Linha {}: @fg precisa de uma cor", number))?;
+                    fg = color_name(value).ok_or(format!("Linha {}: cor desconhecida: {}", number, value))?;
+                }
+                "transparent" => {
+                    // O caractere pode ser um espaço, então lê direto da linha
+                    let value = directive["transparent".len()..].strip_prefix(' ');
+                    transparent = Some(value.and_then(|value| value.chars().next()).unwrap_or(' '));
+                }
+                "opaque" => transparent = None,
+                "clip" => {
+                    let clip = args.next().ok_or(format!("Linha {}: @clip precisa de um nome", number))?;
+                    let mode = match args.next() {
+                        None | Some("loop") => PlayMode::Loop,
+                        Some("pingpong") => PlayMode::PingPong,
+                        Some("once") => PlayMode::Once,
+                        Some(mode) => return Err(format!("Linha {}: modo desconhecido: {}", number, mode).into()),
+                    };
+                    clips.push((clip.to_owned(), mode));
+                }
+                "frame" => {
+                    let duration = match args.next() {
+                        Some(value) => value
+                            .parse::<f32>()
+                            .ok()
+                            .filter(|duration| duration.is_finite() && *duration > 0.0)
+                            .ok_or(format!("Linha {}: duração inválida: {}", number, value))?,
+                        None => DEFAULT_FRAME_DURATION,
+                    };
+                    frames.push(new_frame(&mut clips, duration));
+                }
+                "colors" => {
+                    let frame = frames
+                        .last_mut()
+                        .ok_or(format!("Linha {}: @colors antes de qualquer quadro", number))?;
+                    frame.colors = Some(Vec::new());
+                }
+                _ => return Err(format!("Linha {}: diretiva desconhecida: @{}", number, name).into()),
+            }
+        }
+
+        if frames.is_empty() {
+            return Err("Sprite sem quadros".into());
+        }
+
+        let mut sprite = Self {
+            clips: clips
+                .into_iter()
+                .map(|(name, mode)| (name, Clip::new(mode)))
+                .collect(),
+        };
+        for frame in frames {
+            let mut body = frame_body(&frame, fg)?.with_anchor(anchor.0, anchor.1);
+            body.transparent = transparent;
+            sprite.clips[frame.clip].1.frames.push(Frame { body, duration: frame.duration });
+        }
+        sprite.clips.retain(|(_, clip)| !clip.frames.is_empty());
+        Ok(sprite)
+    }
+
+    // Primeiro quadro do primeiro clipe, para sprites estáticos
+    pub fn body(&self) -> Body {
+        self.clips[0].1.frames[0].body.clone()
+    }
+
+    pub fn clip(&self, name: &str) -> Option<&Clip> {
+        self.clips.iter().find(|(clip, _)| clip == name).map(|(_, clip)| clip)
+    }
+
+    // Animação com todos os clipes; começa pelo primeiro
+    pub fn animation(&self) -> Animation {
+        self.clips
+            .iter()
+            .fold(Animation::new(), |animation, (name, clip)| animation.with_clip(name, clip.clone()))
+    }
+}
+
+// Começa um quadro no clipe atual, criando o clipe "default" se nenhum foi declarado
+fn new_frame(clips: &mut Vec<(String, PlayMode)>, duration: f32) -> RawFrame {
+    if clips.is_empty() {
+        clips.push(("default".to_owned(), PlayMode::Loop));
+    }
+    RawFrame {
+        clip: clips.len() - 1,
+        duration,
+        art: Vec::new(),
+        colors: None,
+    }
+}
+
+fn frame_body(frame: &RawFrame, fg: Color) -> Result<Body, Box<dyn Error>> {
+    // Linhas vazias no fim separam quadros e não fazem parte da arte
+    let trim = |lines: &[String]| {
+        let end = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(0, |end| end + 1);
+        lines[..end].to_vec()
+    };
+    let art = trim(&frame.art);
+    let colors = frame.colors.as_deref().map(trim).unwrap_or_default();
+    if colors.len() > art.len() {
+        return Err("Camada de cores maior que o quadro".into());
+    }
+
+    let lines: Vec<&str> = art.iter().map(String::as_str).collect();
+    let mut body = Body::from_lines(&lines).with_fg(fg);
+    for (row, codes) in body.mat.iter_mut().zip(&colors) {
+        for (cell, code) in row.iter_mut().zip(codes.chars()) {
+            if let Some(color) = color_code(code)? {
+                *cell = Cell { fg: color, ..*cell };
+            }
+        }
+    }
+    Ok(body)
+}
+
+// Uma letra por cor: minúscula é a versão escura, maiúscula a clara
+fn color_code(code: char) -> Result<Option<Color>, Box<dyn Error>> {
+    let color = match code {
+        ' ' | '.' => return Ok(None),
+        'k' => Color::Black,
+        'r' => Color::DarkRed,
+        'R' => Color::Red,
+        'g' => Color::DarkGreen,
+        'G' => Color::Green,
+        'y' => Color::DarkYellow,
+        'Y' => Color::Yellow,
+        'b' => Color::DarkBlue,
+        'B' => Color::Blue,
+        'm' => Color::DarkMagenta,
+        'M' => Color::Magenta,
+        'c' => Color::DarkCyan,
+        'C' => Color::Cyan,
+        'd' => Color::DarkGrey,
+        'w' => Color::Grey,
+        'W' => Color::White,
+        _ => return Err(format!("Código de cor desconhecido: {}", code).into()),
+    };
+    Ok(Some(color))
+}
+
+pub(crate) fn color_name(name: &str) -> Option<Color> {
+    let color = match name.to_lowercase().replace('_', "").as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "darkred" => Color::DarkRed,
+        "red" => Color::Red,
+        "darkgreen" => Color::DarkGreen,
+        "green" => Color::Green,
+        "darkyellow" => Color::DarkYellow,
+        "yellow" => Color::Yellow,
+        "darkblue" => Color::DarkBlue,
+        "blue" => Color::Blue,
+        "darkmagenta" => Color::DarkMagenta,
+        "magenta" => Color::Magenta,
+        "darkcyan" => Color::DarkCyan,
+        "cyan" => Color::Cyan,
+        "darkgrey" | "darkgray" => Color::DarkGrey,
+        "grey" | "gray" => Color::Grey,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_macro_matches_from_lines() {
+        let body = crate::body![" ^^ ", "/00\\", "é", "日"];
+        assert_eq!(body.mat, Body::from_lines(&[" ^^ ", "/00\\", "é", "日"]).mat);
+        assert!(crate::body![].mat.is_empty());
+    }
+}
@@ -0,0 +1,398 @@
+// tilemap.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Tilemaps describe levels as grids of tile ids instead of free-floating Bodies. A palette maps
+// each id to its look (a Cell) and gameplay flags (solid, platform, hazard...), and a map has
+// several layers drawn in order, so decoration can sit on top of the ground. Rendering walks
+// the cells of the current view, not the whole map, so large levels cost only what's visible.
+// Levels are loaded from plain text, one character per tile:
+//
+//   @tile # solid fg=grey          declares the tile used by '#' (flags, fg=, bg=, glyph=)
+//   @layer ground                  starts a layer; the following lines are its rows
+//   @# comment                     @@ escapes a row that starts with '@'
+//
+// Spaces are empty tiles and undeclared characters become plain tiles with that glyph.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::ops::BitOr;
+use std::path::Path;
+
+use crate::Components::IComponent;
+use crate::frame_buffer::{Cell, FrameBuffer};
+use crate::sprite::color_name;
+
+pub type TileId = u16;
+
+// Tile vazio: não desenha nada e não tem flags
+pub const EMPTY: TileId = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TileFlags(pub u8);
+
+impl TileFlags {
+    pub const NONE: TileFlags = TileFlags(0);
+    // Bloqueia movimento
+    pub const SOLID: TileFlags = TileFlags(1);
+    // Só bloqueia quem vem de cima
+    pub const PLATFORM: TileFlags = TileFlags(2);
+    // Causa dano
+    pub const HAZARD: TileFlags = TileFlags(4);
+    // Bloqueia a visão (campo de visão em roguelikes)
+    pub const OPAQUE: TileFlags = TileFlags(8);
+    pub const WATER: TileFlags = TileFlags(16);
+
+    pub fn contains(&self, flags: TileFlags) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    fn from_name(name: &str) -> Option<TileFlags> {
+        let flags = match name {
+            "solid" => Self::SOLID,
+            "platform" => Self::PLATFORM,
+            "hazard" => Self::HAZARD,
+            "opaque" => Self::OPAQUE,
+            "water" => Self::WATER,
+            _ => return None,
+        };
+        Some(flags)
+    }
+}
+
+impl BitOr for TileFlags {
+    type Output = TileFlags;
+
+    fn bitor(self, other: TileFlags) -> TileFlags {
+        TileFlags(self.0 | other.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    pub cell: Cell,
+    pub flags: TileFlags,
+}
+
+impl Tile {
+    pub fn new(cell: Cell) -> Self {
+        Self { cell, flags: TileFlags::NONE }
+    }
+
+    pub fn with_flags(mut self, flags: TileFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TileLayer {
+    pub name: String,
+    pub visible: bool,
+    tiles: Vec<TileId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tilemap {
+    width: u16,
+    height: u16,
+    palette: HashMap<TileId, Tile>,
+    // Caractere usado no arquivo de nível para cada tile
+    keys: HashMap<char, TileId>,
+    layers: Vec<TileLayer>,
+}
+
+impl Tilemap {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            palette: HashMap::new(),
+            keys: HashMap::new(),
+            layers: Vec::new(),
+        }
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    pub fn with_tile(mut self, id: TileId, tile: Tile) -> Self {
+        self.set_tile(id, tile);
+        self
+    }
+
+    pub fn set_tile(&mut self, id: TileId, tile: Tile) {
+        self.palette.insert(id, tile);
+    }
+
+    pub fn tile(&self, id: TileId) -> Option<&Tile> {
+        self.palette.get(&id)
+    }
+
+    // Id do tile representado por um caractere no arquivo de nível
+    pub fn tile_id(&self, key: char) -> Option<TileId> {
+        self.keys.get(&key).copied()
+    }
+
+    // Adiciona uma camada vazia por cima das outras e retorna seu índice
+    pub fn add_layer(&mut self, name: &str) -> usize {
+        self.layers.push(TileLayer {
+            name: name.to_owned(),
+            visible: true,
+            tiles: vec![EMPTY; self.width as usize * self.height as usize],
+        });
+        self.layers.len() - 1
+    }
+
+    pub fn layers(&self) -> &[TileLayer] {
+        &self.layers
+    }
+
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+
+    pub fn set_layer_visible(&mut self, layer: usize, visible: bool) {
+        if let Some(layer) = self.layers.get_mut(layer) {
+            layer.visible = visible;
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(y as usize * self.width as usize + x as usize)
+    }
+
+    pub fn get(&self, layer: usize, x: i32, y: i32) -> TileId {
+        let index = self.index(x, y);
+        self.layers
+            .get(layer)
+            .zip(index)
+            .map_or(EMPTY, |(layer, index)| layer.tiles[index])
+    }
+
+    pub fn set(&mut self, layer: usize, x: i32, y: i32, id: TileId) {
+        if let (Some(index), Some(layer)) = (self.index(x, y), self.layers.get_mut(layer)) {
+            layer.tiles[index] = id;
+        }
+    }
+
+    // Flags de todas as camadas naquela posição; fora do mapa não há nada
+    pub fn flags_at(&self, x: i32, y: i32) -> TileFlags {
+        let Some(index) = self.index(x, y) else {
+            return TileFlags::NONE;
+        };
+        self.layers
+            .iter()
+            .filter_map(|layer| self.palette.get(&layer.tiles[index]))
+            .fold(TileFlags::NONE, |flags, tile| flags | tile.flags)
+    }
+
+    pub fn is_solid(&self, x: i32, y: i32) -> bool {
+        self.flags_at(x, y).contains(TileFlags::SOLID)
+    }
+
+    // Posições de todas as ocorrências de um tile numa camada (ex: pontos de início)
+    pub fn find(&self, layer: usize, id: TileId) -> Vec<(i32, i32)> {
+        let Some(layer) = self.layers.get(layer) else {
+            return Vec::new();
+        };
+        let width = self.width as usize;
+        layer
+            .tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| **tile == id)
+            .map(|(index, _)| ((index % width) as i32, (index / width) as i32))
+            .collect()
+    }
+
+    // Desenha as camadas visíveis; (left, top) é a posição do mapa que aparece no canto da visão.
+    // Só as células da visão são percorridas, independente do tamanho do mapa.
+    pub fn render(&self, frame: &mut FrameBuffer, left: f32, top: f32, zoom: f32) {
+        let (view_width, view_height) = frame.view_size();
+        for screen_y in 0..view_height as i32 {
+            let y = (top + screen_y as f32 / zoom).floor() as i32;
+            if y < 0 || y >= self.height as i32 {
+                continue;
+            }
+            for screen_x in 0..view_width as i32 {
+                let x = (left + screen_x as f32 / zoom).floor() as i32;
+                let Some(index) = self.index(x, y) else {
+                    continue;
+                };
+                for layer in self.layers.iter().filter(|layer| layer.visible) {
+                    if let Some(tile) = self.palette.get(&layer.tiles[index]) {
+                        frame.set(screen_x, screen_y, tile.cell);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut palette: Vec<(char, Tile)> = Vec::new();
+        let mut layers: Vec<(String, Vec<Vec<char>>)> = Vec::new();
+
+        for (number, line) in content.lines().enumerate() {
+            let number = number + 1;
+            let line = line.trim_end_matches('\r');
+
+            let Some(directive) = line.strip_prefix('@').filter(|rest| !rest.starts_with('@')) else {
+                let row = line.strip_prefix('@').unwrap_or(line);
+                match layers.last_mut() {
+                    Some((_, rows)) => rows.push(row.chars().collect()),
+                    None if row.trim().is_empty() => {}
+                    None => return Err(format!("Linha {}: linha do mapa antes de @layer", number).into()),
+                }
+                continue;
+            };
+
+            let mut args = directive.split_whitespace();
+            match args.next().unwrap_or("") {
+                name if name.starts_with('#') => {}
+                "layer" => {
+                    let name = args.next().unwrap_or("layer");
+                    layers.push((name.to_owned(), Vec::new()));
+                }
+                "tile" => {
+                    let key = args
+                        .next()
+                        .and_then(|key| key.chars().next())
+                        .ok_or(format!("Linha {}: @tile precisa de um caractere", number))?;
+                    // Um caractere repetido deixaria o primeiro tile sem uso na paleta
+                    if palette.iter().any(|(declared, _)| *declared == key) {
+                        return Err(format!("Linha {}: tile repetido: {}", number, key).into());
+                    }
+                    let mut tile = Tile::new(Cell::new(key));
+                    for arg in args {
+                        let invalid = || format!("Linha {}: opção de tile inválida: {}", number, arg);
+                        match arg.split_once('=') {
+                            Some(("fg", color)) => tile.cell.fg = color_name(color).ok_or_else(invalid)?,
+                            Some(("bg", color)) => tile.cell.bg = color_name(color).ok_or_else(invalid)?,
+                            Some(("glyph", glyph)) => tile.cell.ch = glyph.chars().next().ok_or_else(invalid)?,
+                            Some(_) => return Err(invalid().into()),
+                            None => tile.flags = tile.flags | TileFlags::from_name(arg).ok_or_else(invalid)?,
+                        }
+                    }
+                    palette.push((key, tile));
+                }
+                name => return Err(format!("Linha {}: diretiva desconhecida: @{}", number, name).into()),
+            }
+        }
+
+        // Linhas vazias no fim de cada camada não fazem parte do mapa
+        for (_, rows) in layers.iter_mut() {
+            while rows.last().is_some_and(|row| row.iter().all(|ch| *ch == ' ')) {
+                rows.pop();
+            }
+        }
+        let width = layers.iter().flat_map(|(_, rows)| rows.iter().map(Vec::len)).max().unwrap_or(0);
+        let height = layers.iter().map(|(_, rows)| rows.len()).max().unwrap_or(0);
+        let too_big = |size: usize| format!("Mapa com {} células de lado; o máximo é {}", size, u16::MAX);
+        let width = u16::try_from(width).map_err(|_| too_big(width))?;
+        let height = u16::try_from(height).map_err(|_| too_big(height))?;
+        let mut map = Tilemap::new(width, height);
+
+        for (key, tile) in palette {
+            map.declare(key, tile);
+        }
+        for (name, rows) in layers {
+            let layer = map.add_layer(&name);
+            for (y, row) in rows.iter().enumerate() {
+                for (x, &key) in row.iter().enumerate() {
+                    if key == ' ' {
+                        continue;
+                    }
+                    let id = match map.tile_id(key) {
+                        Some(id) => id,
+                        None => map.declare(key, Tile::new(Cell::new(key))),
+                    };
+                    map.set(layer, x as i32, y as i32, id);
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    // Registra o tile de um caractere do arquivo com o próximo id livre
+    fn declare(&mut self, key: char, tile: Tile) -> TileId {
+        let id = self.palette.keys().max().map_or(1, |id| id + 1);
+        self.palette.insert(id, tile);
+        self.keys.insert(key, id);
+        id
+    }
+}
+
+impl IComponent for Tilemap {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::style::Color;
+
+    const LEVEL: &str = "\
+@tile # solid fg=grey
+@tile ~ water hazard glyph=≈
+@layer ground
+# ~~
+####
+@layer items
+@@ $
+";
+
+    #[test]
+    fn parse_builds_palette_and_layers() {
+        let map = Tilemap::parse(LEVEL).unwrap();
+        assert_eq!(map.size(), (4, 2));
+        assert_eq!(map.layers().len(), 2);
+
+        let wall = map.tile_id('#').unwrap();
+        let water = map.tile_id('~').unwrap();
+        let coin = map.tile_id('$').unwrap();
+        assert_eq!((wall, water), (1, 2));
+        assert_eq!(map.tile(wall).unwrap().cell.fg, Color::Grey);
+        assert_eq!(map.tile(water).unwrap().cell.ch, '≈');
+        assert_eq!(map.tile(water).unwrap().flags, TileFlags::WATER | TileFlags::HAZARD);
+        // Caracteres não declarados viram tiles simples com o próprio glifo
+        assert_eq!(map.tile(coin).unwrap().cell.ch, '$');
+
+        let ground = map.layer_index("ground").unwrap();
+        let items = map.layer_index("items").unwrap();
+        assert_eq!(map.find(ground, water), vec![(2, 0), (3, 0)]);
+        assert_eq!(map.get(ground, 1, 0), EMPTY);
+        assert_eq!(map.find(items, coin), vec![(2, 0)]);
+        assert_eq!(map.get(items, 0, 0), map.tile_id('@').unwrap());
+        assert!(map.is_solid(0, 1));
+        assert!(!map.is_solid(1, 0));
+    }
+
+    #[test]
+    fn parse_rejects_repeated_tiles() {
+        assert!(Tilemap::parse("@tile # solid\n@tile # water\n@layer a\n#").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_maps_wider_than_u16() {
+        let level = format!("@layer a\n{}", "#".repeat(u16::MAX as usize + 1));
+        assert!(Tilemap::parse(&level).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+// transform.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Render-time transforms for a Body. A `Transform` component flips and rotates the sprite in
+// 90 degree steps, remapping the characters whose shape depends on the direction ('/' and '\',
+// '(' and ')', box-drawing corners...), and a `Tint` component overrides its colors. The
+// DrawSystem applies both to a copy when drawing, so the source Body (and its animation
+// frames) is never mutated and one sprite can face either way.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use std::any::Any;
+
+use crossterm::style::Color;
+
+use crate::Components::{Body, IComponent};
+use crate::frame_buffer::Cell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    None,
+    // Sentido horário
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl Rotation {
+    // Quantidade de giros de 90 graus no sentido horário
+    fn turns(&self) -> usize {
+        match self {
+            Rotation::None => 0,
+            Rotation::Cw90 => 1,
+            Rotation::Cw180 => 2,
+            Rotation::Cw270 => 3,
+        }
+    }
+}
+
+// Espelhamento e rotação aplicados ao desenhar; o espelhamento é feito antes da rotação
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Transform {
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub rotation: Rotation,
+}
+
+impl Transform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_flip_x(mut self, flip: bool) -> Self {
+        self.flip_x = flip;
+        self
+    }
+
+    pub fn with_flip_y(mut self, flip: bool) -> Self {
+        self.flip_y = flip;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl IComponent for Transform {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Cores que substituem as do `body` nas células visíveis; `None` mantém a original
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Tint {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Tint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn with_bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+}
+
+impl IComponent for Tint {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Pares de caracteres trocados ao espelhar na horizontal
+const MIRROR_X: [(char, char); 16] = [
+    ('/', '\\'), ('(', ')'), ('[', ']'), ('{', '}'), ('<', '>'), ('d', 'b'), ('p', 'q'),
+    ('┌', '┐'), ('└', '┘'), ('╔', '╗'), ('╚', '╝'), ('╭', '╮'), ('╰', '╯'), ('├', '┤'),
+    ('▌', '▐'), ('◄', '►'),
+];
+
+// Pares de caracteres trocados ao espelhar na vertical
+const MIRROR_Y: [(char, char); 14] = [
+    ('/', '\\'), ('^', 'v'), ('\'', ','), ('b', 'p'), ('d', 'q'), ('┌', '└'), ('┐', '┘'),
+    ('╔', '╚'), ('╗', '╝'), ('╭', '╰'), ('╮', '╯'), ('┬', '┴'), ('▀', '▄'), ('▲', '▼'),
+];
+
+// Ciclos de caracteres ao girar 90 graus no sentido horário: cada um vira o seguinte
+const ROTATE_CW: [&[char]; 11] = [
+    &['-', '|'],
+    &['/', '\\'],
+    &['─', '│'],
+    &['═', '║'],
+    &['━', '┃'],
+    &['^', '>', 'v', '<'],
+    &['▲', '►', '▼', '◄'],
+    &['┌', '┐', '┘', '└'],
+    &['╔', '╗', '╝', '╚'],
+    &['╭', '╮', '╯', '╰'],
+    &['▀', '▐', '▄', '▌'],
+];
+
+fn mirror(ch: char, pairs: &[(char, char)]) -> char {
+    pairs
+        .iter()
+        .find_map(|&(a, b)| match ch {
+            _ if ch == a => Some(b),
+            _ if ch == b => Some(a),
+            _ => None,
+        })
+        .unwrap_or(ch)
+}
+
+fn rotate_char(ch: char) -> char {
+    ROTATE_CW
+        .iter()
+        .find_map(|cycle| {
+            let index = cycle.iter().position(|&c| c == ch)?;
+            Some(cycle[(index + 1) % cycle.len()])
+        })
+        .unwrap_or(ch)
+}
+
+fn map_cell(cell: Cell, map: impl Fn(char) -> char) -> Cell {
+    if cell.is_continuation() || cell.extra[0] != '\0' {
+        return cell;
+    }
+    Cell { ch: map(cell.ch), ..cell }
+}
+
+impl Body {
+    // Cópia do `body` espelhada e girada; a âncora acompanha o desenho
+    pub fn transformed(&self, transform: &Transform) -> Body {
+        let mut body = self.clone();
+        let (width, height) = body.size();
+
+        if transform.flip_x {
+            for row in body.mat.iter_mut() {
+                // Glifos largos continuam com a continuação à direita
+                let mut units: Vec<Vec<Cell>> = Vec::new();
+                for cell in row.drain(..) {
+                    match units.last_mut() {
+                        Some(unit) if cell.is_continuation() => unit.push(cell),
+                        _ => units.push(vec![cell]),
+                    }
+                }
+                *row = units
+                    .into_iter()
+                    .rev()
+                    .flatten()
+                    .map(|cell| map_cell(cell, |ch| mirror(ch, &MIRROR_X)))
+                    .collect();
+            }
+            body.anchor.0 = width - 1 - body.anchor.0;
+        }
+
+        if transform.flip_y {
+            body.mat.reverse();
+            body.mat
+                .iter_mut()
+                .flatten()
+                .for_each(|cell| *cell = map_cell(*cell, |ch| mirror(ch, &MIRROR_Y)));
+            body.anchor.1 = height - 1 - body.anchor.1;
+        }
+
+        for _ in 0..transform.rotation.turns() {
+            body = body.rotated_cw();
+        }
+        body
+    }
+
+    // Gira 90 graus no sentido horário: a coluna `x` vira a linha `x`, de baixo para cima
+    fn rotated_cw(&self) -> Body {
+        let (width, height) = self.size();
+        // A continuação de um glifo largo perde o sentido depois de girar
+        let blank = Cell::new(self.transparent.unwrap_or(' '));
+
+        let mat = (0..width as usize)
+            .map(|x| {
+                (0..height as usize)
+                    .rev()
+                    .map(|y| match self.mat[y][x] {
+                        cell if cell.is_continuation() => blank,
+                        cell => map_cell(cell, rotate_char),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut body = Body::from_cells(mat).with_anchor(height - 1 - self.anchor.1, self.anchor.0);
+        body.transparent = self.transparent;
+        body
+    }
+
+    // Cópia do `body` com as cores do `tint` nas células visíveis
+    pub fn tinted(&self, tint: &Tint) -> Body {
+        let mut body = self.clone();
+        for row in body.mat.iter_mut() {
+            for cell in row.iter_mut() {
+                if self.is_transparent(cell) {
+                    continue;
+                }
+                cell.fg = tint.fg.unwrap_or(cell.fg);
+                cell.bg = tint.bg.unwrap_or(cell.bg);
+            }
+        }
+        body
+    }
+}
@@ -0,0 +1,57 @@
+// unicode.rs
+
+// --------------------------------------------------------------------------------------------------------------------------------
+// Text measurement in terminal columns. Strings are split into graphemes (user-perceived
+// characters: a base plus combining marks, emoji sequences, flags), and each grapheme takes
+// one or two columns. Bodies, texts and the FrameBuffer use these helpers so that a sprite's
+// columns match what the terminal shows, with wide glyphs followed by a continuation cell.
+// -------------------------------------------------------------------------------------------------------------------------------
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::frame_buffer::Cell;
+
+// Largura de um texto em colunas do terminal
+pub fn width(text: &str) -> usize {
+    text.graphemes(true)
+        .map(|grapheme| Cell::grapheme(grapheme).width() as usize)
+        .sum()
+}
+
+// Células de um texto, uma por coluna: glifos largos são seguidos de `Cell::CONTINUATION`
+pub fn cells(text: &str) -> Vec<Cell> {
+    let mut cells = Vec::with_capacity(text.len());
+    for grapheme in text.graphemes(true) {
+        push_cell(&mut cells, Cell::grapheme(grapheme));
+    }
+    cells
+}
+
+// Adiciona uma célula a uma linha, reservando a coluna extra dos glifos largos
+pub fn push_cell(row: &mut Vec<Cell>, cell: Cell) {
+    row.push(cell);
+    if cell.width() == 2 {
+        row.push(Cell { fg: cell.fg, bg: cell.bg, attrs: cell.attrs, ..Cell::CONTINUATION });
+    }
+}
+
+// Corta um texto em pedaços de no máximo `width` colunas, sem separar grafemas
+pub fn split_width(text: &str, width: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = Cell::grapheme(grapheme).width() as usize;
+        if current_width > 0 && current_width + grapheme_width > width {
+            parts.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        current.push_str(grapheme);
+        current_width += grapheme_width;
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
@@ -0,0 +1,211 @@
+use crossterm::{cursor::{Hide, MoveTo, Show}, execute, style::{Color, Print}};
+use std::io::{stdout, Write};
+
+pub fn crossterm_hello() {
+    execute!(stdout(), Print("Hello Crossterm\n")).expect("Erro ao imprimir no console");
+}
+
+use crossterm::{
+    event::{self, Event, KeyCode},
+    terminal::{self},
+};
+
+use std::time::{Duration, Instant};
+use std::thread::sleep;
+use crate::Components::*;
+use crate::frame_buffer::{Cell, FrameBuffer, Rect};
+use crate::primitives::BorderStyle;
+
+pub fn init(width: u16, height: u16) -> (u16, u16) {
+    // Configuração inicial
+    terminal::enable_raw_mode().expect("Erro ao ativar o modo raw");
+
+    // Obtém o tamanho atual do terminal
+    let (max_cols, max_rows) = terminal::size().expect("Erro ao obter tamanho do console");
+    let mut window = (width, height);
+
+    // Verifica se o tamanho informado está dentro dos limites
+    if width > max_cols || height > max_rows {
+        // Calcula a proporção de ajuste
+        let width_ratio = max_cols as f32 / width as f32;
+        let height_ratio = max_rows as f32 / height as f32;
+
+        // Usa o menor fator para reduzir ambas as dimensões proporcionalmente
+        let scale_factor = width_ratio.min(height_ratio);
+        window.0 = (width as f32 * scale_factor) as u16;
+        window.1 = (height as f32 * scale_factor) as u16;
+    }
+
+    // Configuração de terminal alternativo e ocultação do cursor
+    let mut stdout = stdout();
+    execute!(stdout, terminal::EnterAlternateScreen, Hide).expect("Erro ao configurar terminal alternativo");
+
+    window
+}
+
+fn exit() {
+    let mut stdout = stdout();
+    // Restaura o cursor
+    execute!(stdout, Show).expect("Erro ao mostrar o cursor");
+    // Restaura o estado do terminal
+    terminal::disable_raw_mode().expect("Erro ao desativar o modo raw");
+    execute!(stdout, terminal::LeaveAlternateScreen).expect("Erro ao sair da tela alternativa");
+}
+
+pub fn crossterm_interactive() {
+
+    let mut stdout = stdout();
+    let window = init(16*2, 9);
+    let mut frame = FrameBuffer::new(window.0, window.1);
+
+    // Variável para FPS
+    let target_fps = 60;
+    let frame_duration = Duration::from_millis(1000 / target_fps);
+    let mut state = 0;
+
+    let pos = Position { x: 14.0, y: 5.0 };
+    let body = Body::new(vec![vec!['O'; 3]; 2]);
+
+    // Game loop
+    'game_loop: loop {
+        let start_time = Instant::now();
+
+        // Input - verifica por 'q' para sair
+        if event::poll(Duration::from_millis(0)).expect("Erro ao verificar input") {
+            if let Event::Key(key_event) = event::read().expect("Erro ao ler evento") {
+                if key_event.code == KeyCode::Char('q') {
+                    break 'game_loop;
+                }
+                if key_event.code == KeyCode::Char('a') {
+                    state+=1;
+                }
+            }
+        }
+
+        // Limpeza e atualização - aqui você adiciona a lógica do jogo e o redesenho
+        frame.clear();
+        frame.put_str(0, 0, &format!("Game rodando a {}fps no terminal!", target_fps));
+        frame.put_str(0, 1, &format!("Window of size: {}:{}", window.0, window.1));
+        frame.put_str(0, 2, "Pressione 'q' para sair.");
+        frame.put_str(0, 3, &format!("'a' pressionado: {} vezes", state));
+
+        draw_screen_border(&mut frame, BorderStyle::Char('*'));
+        draw(&body, &pos, &mut frame);
+
+        frame.flush(&mut stdout).expect("Erro ao atualizar terminal");
+
+
+        // Limpa todos os eventos pendentes para evitar o acúmulo de inputs
+        while event::poll(Duration::from_millis(0)).unwrap() {
+            event::read().unwrap(); // Lê e descarta o evento
+        }
+
+        // Controle de FPS
+        let elapsed = start_time.elapsed();
+        if elapsed < frame_duration {
+            sleep(frame_duration - elapsed);
+        }
+    }
+
+    exit();
+}
+
+
+pub fn draw(body: &Body, pos: &Position, frame: &mut FrameBuffer) {
+    // Converte `Position` para inteiros, arredondando
+    draw_at(body, pos.x as i32, pos.y as i32, frame);
+}
+
+// Desenha o `body` com o canto superior esquerdo na célula (start_x, start_y) da tela
+pub fn draw_at(body: &Body, start_x: i32, start_y: i32, frame: &mut FrameBuffer) {
+    // Dimensões do `body`
+    let (body_width, body_height) = body.size();
+    let (start_x, start_y) = (start_x - body.anchor.0, start_y - body.anchor.1);
+
+    // Escreve as células do `body` no frame buffer, que ignora as que ficam fora da janela;
+    // células transparentes deixam aparecer o que foi desenhado antes
+    for row in 0..body_height {
+        for col in 0..body_width {
+            let cell = body.mat[row as usize][col as usize];
+            // A continuação de um glifo largo é escrita junto com ele
+            if !body.is_transparent(&cell) && !cell.is_continuation() {
+                frame.set(start_x + col, start_y + row, cell);
+            }
+        }
+    }
+}
+
+// Desenha o `body` passando cada célula pela câmera, o que permite zoom (ex: minimapas)
+pub fn draw_with_camera(body: &Body, pos: &Position, camera: &Camera, frame: &mut FrameBuffer) {
+    if camera.zoom() == 1.0 {
+        let (x, y) = camera.world_to_screen(pos.x, pos.y);
+        draw_at(body, x, y, frame);
+        return;
+    }
+
+    let (body_width, body_height) = body.size();
+    for row in 0..body_height {
+        for col in 0..body_width {
+            let cell = body.mat[row as usize][col as usize];
+            if !body.is_transparent(&cell) && !cell.is_continuation() {
+                let (x, y) = camera.world_to_screen(
+                    pos.x + (col - body.anchor.0) as f32,
+                    pos.y + (row - body.anchor.1) as f32,
+                );
+                frame.set(x, y, cell);
+            }
+        }
+    }
+}
+
+// Desenha um texto com o canto superior esquerdo do bloco em (x, y), alinhando cada linha
+pub fn draw_text(text: &Text, x: i32, y: i32, frame: &mut FrameBuffer) {
+    let lines = text.lines();
+    let (block_width, _) = text.size();
+
+    for (row, line) in lines.iter().enumerate() {
+        let line_width = crate::unicode::width(line) as i32;
+        let offset = match text.align {
+            TextAlign::Left => 0,
+            TextAlign::Center => (block_width - line_width) / 2,
+            TextAlign::Right => block_width - line_width,
+        };
+
+        for (col, cell) in crate::unicode::cells(line).into_iter().enumerate() {
+            let cell = Cell { fg: text.fg, bg: text.bg, attrs: text.attrs, ..cell };
+            frame.set(x + offset + col as i32, y + row as i32, cell);
+        }
+    }
+}
+
+// Canto superior esquerdo de um texto ancorado na tela (ou na visão atual)
+pub fn anchor_text(text: &Text, offset: (i32, i32), frame: &FrameBuffer) -> (i32, i32) {
+    let (view_width, view_height) = frame.view_size();
+    let (view_width, view_height) = (view_width as i32, view_height as i32);
+    let (width, height) = text.size();
+
+    let x = match text.anchor {
+        Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft | Anchor::World => 0,
+        Anchor::Top | Anchor::Center | Anchor::Bottom => (view_width - width) / 2,
+        Anchor::TopRight | Anchor::Right | Anchor::BottomRight => view_width - width,
+    };
+    let y = match text.anchor {
+        Anchor::TopLeft | Anchor::Top | Anchor::TopRight | Anchor::World => 0,
+        Anchor::Left | Anchor::Center | Anchor::Right => (view_height - height) / 2,
+        Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => view_height - height,
+    };
+
+    (x + offset.0, y + offset.1)
+}
+
+pub fn draw_screen_border(frame: &mut FrameBuffer, style: BorderStyle) {
+    let (win_width, win_height) = frame.size();
+    frame.border(Rect::new(0, 0, win_width, win_height), style, Color::Reset);
+}
+
+pub fn custom_print(message: &str) {
+    let mut stdout = stdout();
+    stdout.flush().expect("Erro ao dar flush na saída");
+    execute!(stdout, MoveTo(0, 0), Print(message)).expect("Erro ao imprimir");
+    stdout.flush().expect("Erro ao dar flush na saída");
+}